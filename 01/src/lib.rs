@@ -0,0 +1,58 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use aoc_common::{Error, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    /// The left and the right location list
+    type Input = (Vec<i64>, Vec<i64>);
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+
+        for line in aoc_common::lines(input) {
+            let parts: Vec<i64> = aoc_common::parse_integers(input, line, ' ')?;
+            let [first, second] = parts[..] else {
+                return Err(Error::parse(input, line, "two location IDs"));
+            };
+            left.push(first);
+            right.push(second);
+        }
+
+        Ok((left, right))
+    }
+
+    fn part1((left, right): &Self::Input) -> Result<impl Display, Error> {
+        let mut left: BinaryHeap<_> = left.iter().copied().map(Reverse).collect();
+        let mut right: BinaryHeap<_> = right.iter().copied().map(Reverse).collect();
+
+        // calculate distances
+        let mut total_distance = 0;
+        while let (Some(Reverse(l)), Some(Reverse(r))) = (left.pop(), right.pop()) {
+            total_distance += (l - r).abs();
+        }
+
+        Ok(total_distance)
+    }
+
+    fn part2((left, right): &Self::Input) -> Result<impl Display, Error> {
+        let mut right_hits: HashMap<i64, i64> = HashMap::new();
+        for r in right {
+            *right_hits.entry(*r).or_insert(0) += 1;
+        }
+
+        let mut similarity = 0;
+        for l in left {
+            if let Some(hits) = right_hits.get(l) {
+                similarity += l * hits;
+            }
+        }
+
+        Ok(similarity)
+    }
+}
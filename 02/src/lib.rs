@@ -0,0 +1,45 @@
+use std::fmt::Display;
+
+use aoc_common::{Error, Solution};
+use itertools::Itertools;
+
+pub struct Day02;
+
+fn is_safe(levels: &[i64]) -> bool {
+    let ascending = levels.windows(2).all(|w| w[0] < w[1]);
+    let descending = levels.windows(2).all(|w| w[0] > w[1]);
+    let correct_diff = levels.windows(2).all(|w| {
+        let delta = (w[0] - w[1]).abs();
+        (1..=3).contains(&delta)
+    });
+
+    correct_diff && (ascending || descending)
+}
+
+impl Solution for Day02 {
+    /// The levels of every report
+    type Input = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        aoc_common::lines(input)
+            .map(|line| aoc_common::parse_integers(input, line, ' '))
+            .collect()
+    }
+
+    fn part1(reports: &Self::Input) -> Result<impl Display, Error> {
+        Ok(reports.iter().filter(|levels| is_safe(levels)).count())
+    }
+
+    fn part2(reports: &Self::Input) -> Result<impl Display, Error> {
+        Ok(reports
+            .iter()
+            .filter(|levels| {
+                levels
+                    .iter()
+                    .copied()
+                    .combinations(levels.len() - 1)
+                    .any(|subset| is_safe(&subset))
+            })
+            .count())
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt::Display;
+
+use aoc_common::{Error, Solution};
+use regex::Regex;
+
+pub struct Day03;
+
+fn mul_sum(input: &str) -> u64 {
+    let r = Regex::new(r"mul\((\d\d?\d?),(\d\d?\d?)\)").unwrap();
+
+    r.captures_iter(input)
+        .map(|c| c.extract())
+        .map(|(_, [x, y])| {
+            let x = x.parse::<u64>().unwrap();
+            let y = y.parse::<u64>().unwrap();
+            x * y
+        })
+        .sum()
+}
+
+impl Solution for Day03 {
+    /// The corrupted memory
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display, Error> {
+        Ok(mul_sum(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display, Error> {
+        let mut filtered_input = String::new();
+
+        let mut line_rest = input.as_str();
+        loop {
+            if let Some(index) = line_rest.find("don't()") {
+                filtered_input.push_str(&line_rest[0..index]);
+                line_rest = &line_rest[index..];
+                if let Some(index) = line_rest.find("do()") {
+                    line_rest = &line_rest[index..];
+                } else {
+                    break;
+                }
+            } else {
+                filtered_input.push_str(line_rest);
+                break;
+            }
+        }
+
+        Ok(mul_sum(&filtered_input))
+    }
+}
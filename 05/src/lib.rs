@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use aoc_common::{Error, Solution};
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct PageOrderingRules {
+    rules: HashMap<u64, Vec<u64>>,
+    empty_vec: Vec<u64>,
+}
+
+impl PageOrderingRules {
+    fn get_succesors(&self, number: u64) -> &Vec<u64> {
+        self.rules.get(&number).unwrap_or(&self.empty_vec)
+    }
+
+    /// Parses one `X|Y` rule per line of `section`, which has to be a slice of `input`.
+    fn parse(input: &str, section: &str) -> Result<Self, Error> {
+        let mut rules: HashMap<u64, Vec<u64>> = HashMap::new();
+
+        for line in aoc_common::lines(section) {
+            let parts: Vec<u64> = aoc_common::parse_integers(input, line, '|')?;
+            let [a, b] = parts[..] else {
+                return Err(Error::parse(input, line, "a page ordering rule `X|Y`"));
+            };
+            rules.entry(a).or_default().push(b);
+        }
+
+        Ok(Self {
+            rules,
+            empty_vec: Vec::new(),
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct ManualUpdate {
+    pub pages: Vec<u64>,
+}
+
+impl ManualUpdate {
+    /// Parses the comma separated pages of `line`, which has to be a slice of `input`.
+    fn parse(input: &str, line: &str) -> Result<Self, Error> {
+        let pages: Vec<u64> = aoc_common::parse_integers(input, line, ',')?;
+        if pages.is_empty() {
+            return Err(Error::parse(input, line, "a list of page numbers"));
+        }
+        Ok(Self { pages })
+    }
+
+    pub fn middle_page(&self) -> u64 {
+        *self.pages.get((self.pages.len() - 1) / 2).unwrap_or(&0)
+    }
+
+    pub fn is_correctly_ordered(&self, rules: &PageOrderingRules) -> bool {
+        (0..self.pages.len()).all(|i| {
+            let succesors = rules.get_succesors(self.pages[i]);
+            !self.pages[0..i].iter().any(|n| succesors.contains(n))
+        })
+    }
+
+    /// Sorts the pages topologically according to the rules that apply to this update.
+    /// Fails if these rules contain a cycle, because then no valid order exists.
+    pub fn reordered(&self, rules: &PageOrderingRules) -> Result<ManualUpdate, Error> {
+        let mut in_degree: HashMap<u64, usize> = self.pages.iter().map(|p| (*p, 0)).collect();
+        for page in &self.pages {
+            for succesor in rules.get_succesors(*page) {
+                if let Some(degree) = in_degree.get_mut(succesor) {
+                    *degree += 1;
+                }
+            }
+        }
+
+        let mut remaining = self.pages.clone();
+        let mut pages = Vec::with_capacity(self.pages.len());
+        while !remaining.is_empty() {
+            let Some(index) = remaining.iter().position(|p| in_degree[p] == 0) else {
+                return Err(Error::Unsolvable(format!(
+                    "Page ordering rules contain a cycle for update {:?}",
+                    self.pages
+                )));
+            };
+
+            let page = remaining.remove(index);
+            for succesor in rules.get_succesors(page) {
+                if let Some(degree) = in_degree.get_mut(succesor) {
+                    *degree -= 1;
+                }
+            }
+            pages.push(page);
+        }
+
+        Ok(ManualUpdate { pages })
+    }
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    rules: PageOrderingRules,
+    pages: Vec<ManualUpdate>,
+}
+
+impl Puzzle {
+    pub fn new(rules: PageOrderingRules, pages: Vec<ManualUpdate>) -> Self {
+        Self { rules, pages }
+    }
+
+    pub fn correctly_ordered_middle_number_sum(&self) -> u64 {
+        self.pages
+            .iter()
+            .filter(|p| p.is_correctly_ordered(&self.rules))
+            .map(|p| p.middle_page())
+            .sum()
+    }
+
+    pub fn reordered_middle_number_sum(&self) -> Result<u64, Error> {
+        self.pages
+            .iter()
+            .filter(|p| !p.is_correctly_ordered(&self.rules))
+            .map(|p| p.reordered(&self.rules).map(|p| p.middle_page()))
+            .sum()
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let sections = aoc_common::sections(input);
+
+        let rules = PageOrderingRules::parse(input, sections.first().unwrap_or(&""))?;
+        let pages = sections
+            .get(1)
+            .map(|s| {
+                aoc_common::lines(s)
+                    .map(|line| ManualUpdate::parse(input, line))
+                    .collect()
+            })
+            .transpose()?
+            .unwrap_or_default();
+        if let Some(extra) = sections.get(2) {
+            return Err(Error::parse(input, extra, "end of input"));
+        }
+
+        Ok(Puzzle::new(rules, pages))
+    }
+
+    fn part1(puzzle: &Self::Input) -> Result<impl Display, Error> {
+        Ok(puzzle.correctly_ordered_middle_number_sum())
+    }
+
+    fn part2(puzzle: &Self::Input) -> Result<impl Display, Error> {
+        puzzle.reordered_middle_number_sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pagerules() {
+        let input = "47|53\n97|13\n97|61";
+        let actual = PageOrderingRules::parse(input, input).unwrap();
+
+        let mut rules = HashMap::new();
+        rules.insert(47, vec![53]);
+        rules.insert(97, vec![13, 61]);
+        let expected = PageOrderingRules {
+            rules,
+            empty_vec: Vec::new(),
+        };
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_parse_update() {
+        let input = "75,47,61,53,29";
+        let actual = ManualUpdate::parse(input, input).unwrap();
+
+        let expected = ManualUpdate {
+            pages: vec![75, 47, 61, 53, 29],
+        };
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_middle_page() {
+        let test = ManualUpdate {
+            pages: vec![75, 47, 61, 53, 29],
+        };
+        assert_eq!(61, test.middle_page());
+
+        let test = ManualUpdate {
+            pages: vec![75, 29, 13],
+        };
+        assert_eq!(29, test.middle_page());
+
+        let test = ManualUpdate {
+            pages: vec![1, 2, 3, 4, 5, 6, 7, 8, 9],
+        };
+        assert_eq!(5, test.middle_page());
+    }
+
+    #[test]
+    fn test_reorder_update() {
+        let input = "97|75\n75|47\n47|61\n97|47\n61|13";
+        let rules = PageOrderingRules::parse(input, input).unwrap();
+        let update = ManualUpdate {
+            pages: vec![61, 13, 47, 75, 97],
+        };
+        assert!(!update.is_correctly_ordered(&rules));
+
+        let actual = update.reordered(&rules).unwrap();
+        assert_eq!(vec![97, 75, 47, 61, 13], actual.pages);
+        assert!(actual.is_correctly_ordered(&rules));
+    }
+
+    #[test]
+    fn test_reorder_cycle() {
+        let input = "1|2\n2|3\n3|1";
+        let rules = PageOrderingRules::parse(input, input).unwrap();
+        let update = ManualUpdate {
+            pages: vec![3, 2, 1],
+        };
+        assert!(update.reordered(&rules).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let actual = Day05::parse("47|53\n97|13|61\n\n75,47").unwrap_err();
+        assert_eq!(
+            actual,
+            Error::Parse {
+                line: 2,
+                column: 1,
+                text: "97|13|61".to_string(),
+                expected: "a page ordering rule `X|Y`".to_string(),
+            }
+        );
+
+        let actual = Day05::parse("47|53\n\n75,47\n75;47").unwrap_err();
+        assert_eq!(
+            actual.to_string(),
+            "line 4, column 1: expected an integer, found `75;47`"
+        );
+
+        let actual = Day05::parse("1|2\n\n1,2,3\n\n2,1,3\n").unwrap_err();
+        assert_eq!(
+            actual.to_string(),
+            "line 5, column 1: expected end of input, found `2,1,3`"
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{bus_bit, Operator, Puzzle, WireId};
+
+/// The role of a gate in a ripple-carry adder.
+///
+/// Bit `i > 0` is built as `x XOR y -> a` (input XOR), `x AND y -> b` (input AND),
+/// `a XOR carry -> z` (sum XOR), `a AND carry -> d` (carry AND) and `b OR d -> carry'`
+/// (carry OR). Bit 0 only has the input gates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GateRole {
+    InputXor,
+    InputAnd,
+    SumXor,
+    CarryAnd,
+    CarryOr,
+}
+
+/// The role of one gate and, if it breaks the pattern of that role, what was expected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GateAnalysis {
+    /// The gate in the input format.
+    pub gate: String,
+    /// The wire the gate drives.
+    pub output: String,
+    /// The role suggested by the operator and operands, if there is one.
+    pub role: Option<GateRole>,
+    /// The bit of the input gates, taken from their operands.
+    pub bit: Option<usize>,
+    /// The expected pattern the gate breaks, `None` if it fits its role.
+    pub problem: Option<String>,
+}
+
+/// The roles of all gates of a circuit that should be a ripple-carry adder.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AdderAnalysis {
+    /// The number of bits of the `x` bus.
+    pub bits: usize,
+    /// All gates, in the order of the circuit.
+    pub gates: Vec<GateAnalysis>,
+}
+
+impl AdderAnalysis {
+    /// Returns the gates that fit no valid role.
+    pub fn problems(&self) -> impl Iterator<Item = &GateAnalysis> {
+        self.gates.iter().filter(|gate| gate.problem.is_some())
+    }
+}
+
+impl fmt::Display for GateRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            GateRole::InputXor => "input XOR",
+            GateRole::InputAnd => "input AND",
+            GateRole::SumXor => "sum XOR",
+            GateRole::CarryAnd => "carry AND",
+            GateRole::CarryOr => "carry OR",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Lists every gate with its role, followed by the gates that fit no role.
+impl fmt::Display for AdderAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.gates.iter().map(|g| g.gate.len()).max().unwrap_or(0);
+        for gate in &self.gates {
+            write!(f, "{:width$}  ", gate.gate)?;
+            match (gate.role, gate.bit) {
+                (Some(role), Some(bit)) => writeln!(f, "{role}, bit {bit}")?,
+                (Some(role), None) => writeln!(f, "{role}")?,
+                (None, _) => writeln!(f, "no role")?,
+            }
+        }
+
+        let problems: Vec<&GateAnalysis> = self.problems().collect();
+        if problems.is_empty() {
+            return writeln!(f, "\nEvery gate fits its role");
+        }
+        writeln!(f, "\n{} gates fit no valid role:", problems.len())?;
+        for gate in problems {
+            let role = gate.role.map_or("no role".to_string(), |r| r.to_string());
+            let problem = gate.problem.as_deref().unwrap_or_default();
+            writeln!(f, "{:width$}  {role}: {problem}", gate.gate)?;
+        }
+        Ok(())
+    }
+}
+
+impl Puzzle {
+    /// Classifies every gate by its role in a ripple-carry adder over the `x`, `y` and `z`
+    /// buses and checks that its output is used the way the role requires.
+    pub fn analyze_adder(&self) -> AdderAnalysis {
+        let bits = self.bus_width("x");
+        let mut consumers: Vec<Vec<Operator>> = vec![Vec::new(); self.wires.len()];
+        for sentence in &self.queue {
+            for operand in &sentence.operands {
+                consumers[*operand].push(sentence.operator);
+            }
+        }
+        let feeds = |wire: WireId, operator: Operator| consumers[wire].contains(&operator);
+        let feeds_next_bit = |wire| feeds(wire, Operator::Xor) && feeds(wire, Operator::And);
+
+        let gates = self
+            .queue
+            .iter()
+            .map(|sentence| {
+                let output = self.name(sentence.result);
+                let output_bit = bus_bit(output, "z");
+                let input_bit = self.input_bit(&sentence.operands);
+                let internal = sentence
+                    .operands
+                    .iter()
+                    .all(|w| !self.solution.contains_key(w));
+
+                let (role, problem) = match (sentence.operator, input_bit) {
+                    (Operator::Xor, Some(0)) => (
+                        Some(GateRole::InputXor),
+                        (output != "z00").then(|| "expected to drive z00".to_string()),
+                    ),
+                    (Operator::Xor, Some(_)) => (
+                        Some(GateRole::InputXor),
+                        (output_bit.is_some() || !feeds_next_bit(sentence.result)).then(|| {
+                            "expected to feed a sum XOR and a carry AND".to_string()
+                        }),
+                    ),
+                    (Operator::And, Some(0)) if bits == 1 => (
+                        Some(GateRole::InputAnd),
+                        (output != "z01").then(|| "expected to drive z01".to_string()),
+                    ),
+                    (Operator::And, Some(0)) => (
+                        Some(GateRole::InputAnd),
+                        (output_bit.is_some() || !feeds_next_bit(sentence.result)).then(|| {
+                            "expected to be the carry into bit 1 and feed a sum XOR and a \
+                            carry AND"
+                                .to_string()
+                        }),
+                    ),
+                    (Operator::And, Some(_)) => (
+                        Some(GateRole::InputAnd),
+                        (output_bit.is_some() || !feeds(sentence.result, Operator::Or))
+                            .then(|| "expected to feed a carry OR".to_string()),
+                    ),
+                    (Operator::Xor, None) if internal => (
+                        Some(GateRole::SumXor),
+                        (!output_bit.is_some_and(|bit| bit > 0 && bit < bits)).then(|| {
+                            format!(
+                                "expected to drive one of the outputs z01 to z{:02}",
+                                bits.saturating_sub(1)
+                            )
+                        }),
+                    ),
+                    (Operator::And, None) if internal => (
+                        Some(GateRole::CarryAnd),
+                        (output_bit.is_some() || !feeds(sentence.result, Operator::Or))
+                            .then(|| "expected to feed a carry OR".to_string()),
+                    ),
+                    (Operator::Or, None) if internal => {
+                        let last = output_bit == Some(bits);
+                        let carry = output_bit.is_none() && feeds_next_bit(sentence.result);
+                        (
+                            Some(GateRole::CarryOr),
+                            (!last && !carry).then(|| {
+                                format!(
+                                    "expected to feed a sum XOR and a carry AND, or to drive z{bits:02}"
+                                )
+                            }),
+                        )
+                    }
+                    (Operator::And | Operator::Or | Operator::Xor, _) => (
+                        None,
+                        Some(
+                            "expected the x and y wires of one bit or two internal wires as operands"
+                                .to_string(),
+                        ),
+                    ),
+                    _ => (
+                        None,
+                        Some(format!(
+                            "expected an AND, OR or XOR gate, found {}",
+                            sentence.operator
+                        )),
+                    ),
+                };
+
+                GateAnalysis {
+                    gate: sentence.display(&self.wires).to_string(),
+                    output: output.to_string(),
+                    role,
+                    bit: input_bit.filter(|_| role.is_some()),
+                    problem,
+                }
+            })
+            .collect();
+
+        AdderAnalysis { bits, gates }
+    }
+
+    /// Returns the bit if the operands are the `x` and `y` wires of the same bit.
+    fn input_bit(&self, operands: &[WireId]) -> Option<usize> {
+        let [a, b] = operands else {
+            return None;
+        };
+        let (a, b) = (self.name(*a), self.name(*b));
+        let bit = bus_bit(a, "x").or_else(|| bus_bit(a, "y"))?;
+        let expected = if a.starts_with('x') { "y" } else { "x" };
+        (bus_bit(b, expected) == Some(bit)).then_some(bit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::adder;
+
+    fn roles(analysis: &AdderAnalysis, role: GateRole) -> usize {
+        analysis
+            .gates
+            .iter()
+            .filter(|g| g.role == Some(role))
+            .count()
+    }
+
+    #[test]
+    fn classifies_correct_adder() {
+        let analysis = adder(6, 0, 0, &[]).analyze_adder();
+
+        assert_eq!(analysis.problems().count(), 0);
+        assert_eq!(roles(&analysis, GateRole::InputXor), 6);
+        assert_eq!(roles(&analysis, GateRole::InputAnd), 6);
+        assert_eq!(roles(&analysis, GateRole::SumXor), 5);
+        assert_eq!(roles(&analysis, GateRole::CarryAnd), 5);
+        assert_eq!(roles(&analysis, GateRole::CarryOr), 5);
+        assert_eq!(analysis.gates[2].bit, Some(1));
+        assert!(analysis
+            .to_string()
+            .ends_with("\nEvery gate fits its role\n"));
+    }
+
+    #[test]
+    fn reports_swapped_outputs() {
+        let puzzle = adder(
+            6,
+            0,
+            0,
+            &[
+                ("z00", "co00"),
+                ("hs01", "hc01"),
+                ("z03", "cd03"),
+                ("z04", "co04"),
+            ],
+        );
+        let analysis = puzzle.analyze_adder();
+
+        let mut outputs: Vec<&str> = analysis.problems().map(|g| g.output.as_str()).collect();
+        outputs.sort();
+        assert_eq!(
+            outputs,
+            ["cd03", "co00", "co04", "hc01", "hs01", "z00", "z03", "z04"]
+        );
+
+        let sum = analysis.gates.iter().find(|g| g.output == "cd03").unwrap();
+        assert_eq!(sum.role, Some(GateRole::SumXor));
+        assert_eq!(
+            sum.problem.as_deref(),
+            Some("expected to drive one of the outputs z01 to z05")
+        );
+        assert!(analysis
+            .to_string()
+            .contains("\n8 gates fit no valid role:\n"));
+    }
+
+    #[test]
+    fn reports_gates_without_role() {
+        let puzzle: Puzzle = "x00: 0\nx01: 0\ny00: 0\ny01: 0\n\nx00 XOR y01 -> z00\n\
+            x00 NAND y00 -> z01"
+            .parse()
+            .unwrap();
+        let analysis = puzzle.analyze_adder();
+
+        assert_eq!(analysis.gates[0].role, None);
+        assert_eq!(
+            analysis.gates[1].problem.as_deref(),
+            Some("expected an AND, OR or XOR gate, found NAND")
+        );
+    }
+
+    #[test]
+    fn serializes_to_json() {
+        let analysis = adder(2, 0, 0, &[]).analyze_adder();
+        let json = serde_json::to_value(&analysis).unwrap();
+
+        assert_eq!(json["bits"], 2);
+        assert_eq!(json["gates"][0]["gate"], "x00 XOR y00 -> z00");
+        assert_eq!(json["gates"][0]["role"], "input_xor");
+        assert_eq!(json["gates"][0]["bit"], 0);
+        assert_eq!(json["gates"][0]["problem"], serde_json::Value::Null);
+    }
+}
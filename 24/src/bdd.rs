@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use crate::{CircuitError, Operator};
+
+/// The node of the constant function `0`.
+pub(crate) const FALSE: usize = 0;
+/// The node of the constant function `1`.
+pub(crate) const TRUE: usize = 1;
+
+/// Upper bound for the number of nodes, so a badly ordered circuit fails instead of
+/// exhausting the memory.
+const NODE_LIMIT: usize = 1 << 22;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Node {
+    variable: usize,
+    low: usize,
+    high: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum BinaryOp {
+    And,
+    Or,
+    Xor,
+}
+
+/// A reduced ordered binary decision diagram. Functions are node indices; two functions are
+/// equal exactly if their nodes are equal.
+pub(crate) struct Bdd {
+    nodes: Vec<Node>,
+    unique: HashMap<Node, usize>,
+    cache: HashMap<(BinaryOp, usize, usize), usize>,
+}
+
+impl Bdd {
+    pub(crate) fn new() -> Self {
+        let terminal = Node {
+            variable: usize::MAX,
+            low: FALSE,
+            high: TRUE,
+        };
+        Bdd {
+            nodes: vec![terminal, terminal],
+            unique: HashMap::new(),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Returns the function that is the value of the variable with the given index.
+    /// Variables with lower indices are closer to the root.
+    pub(crate) fn variable(&mut self, variable: usize) -> Result<usize, CircuitError> {
+        self.node(variable, FALSE, TRUE)
+    }
+
+    /// Returns the function computed by a gate with the given operand functions.
+    pub(crate) fn gate(
+        &mut self,
+        operator: &Operator,
+        operands: &[usize],
+    ) -> Result<usize, CircuitError> {
+        let [a, b] = match operands {
+            [a, b] => [*a, *b],
+            [a] => [*a, TRUE],
+            _ => [FALSE, FALSE],
+        };
+        match operator {
+            Operator::And => self.apply(BinaryOp::And, a, b),
+            Operator::Or => self.apply(BinaryOp::Or, a, b),
+            Operator::Xor => self.apply(BinaryOp::Xor, a, b),
+            Operator::Nand => {
+                let and = self.apply(BinaryOp::And, a, b)?;
+                self.apply(BinaryOp::Xor, and, TRUE)
+            }
+            Operator::Nor => {
+                let or = self.apply(BinaryOp::Or, a, b)?;
+                self.apply(BinaryOp::Xor, or, TRUE)
+            }
+            Operator::Xnor => {
+                let xor = self.apply(BinaryOp::Xor, a, b)?;
+                self.apply(BinaryOp::Xor, xor, TRUE)
+            }
+            Operator::Not => self.apply(BinaryOp::Xor, a, TRUE),
+            Operator::Zero => Ok(FALSE),
+            Operator::One => Ok(TRUE),
+        }
+    }
+
+    /// Returns the function that is `1` wherever `a` and `b` differ.
+    pub(crate) fn difference(&mut self, a: usize, b: usize) -> Result<usize, CircuitError> {
+        self.apply(BinaryOp::Xor, a, b)
+    }
+
+    /// Returns variable values for which `function` is `1`. Variables that aren't listed
+    /// can have any value.
+    pub(crate) fn satisfying_assignment(&self, function: usize) -> Option<Vec<(usize, bool)>> {
+        if function == FALSE {
+            return None;
+        }
+
+        let mut assignment = Vec::new();
+        let mut current = function;
+        while current != TRUE {
+            let node = self.nodes[current];
+            // every node other than FALSE leads to TRUE, so only FALSE has to be avoided
+            if node.low != FALSE {
+                assignment.push((node.variable, false));
+                current = node.low;
+            } else {
+                assignment.push((node.variable, true));
+                current = node.high;
+            }
+        }
+        Some(assignment)
+    }
+
+    fn node(&mut self, variable: usize, low: usize, high: usize) -> Result<usize, CircuitError> {
+        if low == high {
+            return Ok(low);
+        }
+
+        let node = Node {
+            variable,
+            low,
+            high,
+        };
+        if let Some(index) = self.unique.get(&node) {
+            return Ok(*index);
+        }
+        if self.nodes.len() >= NODE_LIMIT {
+            return Err(CircuitError::TooComplex { nodes: NODE_LIMIT });
+        }
+
+        let index = self.nodes.len();
+        self.nodes.push(node);
+        self.unique.insert(node, index);
+        Ok(index)
+    }
+
+    fn apply(&mut self, op: BinaryOp, a: usize, b: usize) -> Result<usize, CircuitError> {
+        let (a, b) = (a.min(b), a.max(b));
+        let terminal = match op {
+            BinaryOp::And if a == FALSE => Some(FALSE),
+            BinaryOp::And if a == TRUE || a == b => Some(b),
+            BinaryOp::Or if a == FALSE || a == b => Some(b),
+            BinaryOp::Or if a == TRUE => Some(TRUE),
+            BinaryOp::Xor if a == b => Some(FALSE),
+            BinaryOp::Xor if a == FALSE => Some(b),
+            BinaryOp::Xor if a == TRUE && b == TRUE => Some(FALSE),
+            _ => None,
+        };
+        if let Some(result) = terminal {
+            return Ok(result);
+        }
+        if let Some(result) = self.cache.get(&(op, a, b)) {
+            return Ok(*result);
+        }
+
+        let (node_a, node_b) = (self.nodes[a], self.nodes[b]);
+        let variable = node_a.variable.min(node_b.variable);
+        let cofactors = |node: Node, index: usize| {
+            if node.variable == variable {
+                (node.low, node.high)
+            } else {
+                (index, index)
+            }
+        };
+        let (a_low, a_high) = cofactors(node_a, a);
+        let (b_low, b_high) = cofactors(node_b, b);
+
+        let low = self.apply(op, a_low, b_low)?;
+        let high = self.apply(op, a_high, b_high)?;
+        let result = self.node(variable, low, high)?;
+        self.cache.insert((op, a, b), result);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_functions_share_nodes() {
+        let mut bdd = Bdd::new();
+        let a = bdd.variable(0).unwrap();
+        let b = bdd.variable(1).unwrap();
+
+        // a XOR b == (a OR b) AND (a NAND b)
+        let xor = bdd.gate(&Operator::Xor, &[a, b]).unwrap();
+        let or = bdd.gate(&Operator::Or, &[a, b]).unwrap();
+        let nand = bdd.gate(&Operator::Nand, &[a, b]).unwrap();
+        assert_eq!(bdd.gate(&Operator::And, &[or, nand]).unwrap(), xor);
+
+        let not_a = bdd.gate(&Operator::Not, &[a]).unwrap();
+        assert_eq!(bdd.gate(&Operator::Not, &[not_a]).unwrap(), a);
+        assert_eq!(bdd.gate(&Operator::Xnor, &[a, not_a]).unwrap(), FALSE);
+        assert_eq!(bdd.gate(&Operator::One, &[]).unwrap(), TRUE);
+    }
+
+    #[test]
+    fn finds_satisfying_assignments() {
+        let mut bdd = Bdd::new();
+        let a = bdd.variable(0).unwrap();
+        let b = bdd.variable(1).unwrap();
+        let not_a = bdd.gate(&Operator::Not, &[a]).unwrap();
+        let function = bdd.gate(&Operator::And, &[not_a, b]).unwrap();
+
+        assert_eq!(
+            bdd.satisfying_assignment(function),
+            Some(vec![(0, false), (1, true)])
+        );
+        assert_eq!(bdd.satisfying_assignment(FALSE), None);
+        assert_eq!(bdd.satisfying_assignment(TRUE), Some(Vec::new()));
+    }
+}
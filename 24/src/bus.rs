@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use num_bigint::BigUint;
+
+use crate::{CircuitError, Puzzle};
+
+/// The input and output buses of an adder.
+pub(crate) const BUSES: [&str; 3] = ["x", "y", "z"];
+
+/// Splits a wire name into its bus and bit index, e.g. `("x", 5)` for `x05`. The bit index
+/// is the number at the end of the name.
+pub(crate) fn split_bus(wire: &str) -> Option<(&str, usize)> {
+    let bus = wire.trim_end_matches(|c: char| c.is_ascii_digit());
+    if bus.is_empty() || bus.len() == wire.len() {
+        return None;
+    }
+    Some((bus, wire[bus.len()..].parse().ok()?))
+}
+
+/// Returns the bit index of `wire` if it belongs to the bus `prefix`, e.g. `5` for `x05`.
+pub fn bus_bit(wire: &str, prefix: &str) -> Option<usize> {
+    split_bus(wire).and_then(|(bus, bit)| (bus == prefix).then_some(bit))
+}
+
+/// Reads the value of a bus from wire values by name. Bit `i` is taken from wire `{prefix}{i}`.
+pub(crate) fn bus_value_by_name(values: &HashMap<String, u8>, prefix: &str) -> BigUint {
+    let mut value = BigUint::default();
+    for (wire, bit_value) in values {
+        if let Some(bit) = bus_bit(wire, prefix) {
+            value.set_bit(bit as u64, *bit_value == 1);
+        }
+    }
+    value
+}
+
+impl Puzzle {
+    /// Returns the number of bits of a bus, counting all wires of the circuit that belong to it.
+    pub fn bus_width(&self, prefix: &str) -> usize {
+        self.wires.bus(prefix).last().map_or(0, |(bit, _)| bit + 1)
+    }
+
+    /// Returns the number of bits of a bus, counting only the initial wires.
+    pub(crate) fn input_width(&self, prefix: &str) -> usize {
+        self.solution
+            .keys()
+            .filter_map(|wire| bus_bit(self.name(*wire), prefix))
+            .map(|bit| bit + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Sets the initial values of the input wires of a bus to the bits of `value`.
+    pub fn set_bus(&mut self, prefix: &str, value: &BigUint) -> Result<(), CircuitError> {
+        let width = self.input_width(prefix);
+        if value.bits() > width as u64 {
+            return Err(CircuitError::BusOverflow {
+                bus: prefix.to_string(),
+                width,
+            });
+        }
+
+        for (wire, bit_value) in self.solution.iter_mut() {
+            if let Some(bit) = bus_bit(self.wires.name(*wire), prefix) {
+                *bit_value = u8::from(value.bit(bit as u64));
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluates the circuit and returns the value of the bus `prefix`.
+    pub fn read_bus(&self, prefix: &str) -> Result<BigUint, CircuitError> {
+        let values = self.evaluate()?;
+        let mut value = BigUint::default();
+        for (bit, wire) in self.wires.bus(prefix) {
+            value.set_bit(bit as u64, values[wire] == 1);
+        }
+        Ok(value)
+    }
+
+    /// Evaluates the circuit for the given input bus values and returns the value of the
+    /// output bus. The initial values of the circuit stay unchanged.
+    pub fn compute(
+        &self,
+        inputs: &[(&str, &BigUint)],
+        output: &str,
+    ) -> Result<BigUint, CircuitError> {
+        let mut puzzle = self.clone();
+        for (prefix, value) in inputs {
+            puzzle.set_bus(prefix, value)?;
+        }
+        puzzle.read_bus(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::adder;
+
+    #[test]
+    fn bus_bits() {
+        assert_eq!(bus_bit("z07", "z"), Some(7));
+        assert_eq!(bus_bit("x123", "x"), Some(123));
+        assert_eq!(bus_bit("co05", "co"), Some(5));
+        assert_eq!(bus_bit("cd05", "c"), None);
+        assert_eq!(bus_bit("z", "z"), None);
+
+        assert_eq!(split_bus("hs12"), Some(("hs", 12)));
+        assert_eq!(split_bus("a1b05"), Some(("a1b", 5)));
+        assert_eq!(split_bus("cd"), None);
+        assert_eq!(split_bus("42"), None);
+    }
+
+    #[test]
+    fn reads_initial_values() {
+        let puzzle = adder(5, 13, 22, &[]);
+        assert_eq!(puzzle.bus_width("x"), 5);
+        assert_eq!(puzzle.bus_width("z"), 6);
+        assert_eq!(puzzle.read_bus("x"), Ok(BigUint::from(13_u32)));
+        assert_eq!(puzzle.read_bus("y"), Ok(BigUint::from(22_u32)));
+        assert_eq!(puzzle.read_bus("z"), Ok(BigUint::from(35_u32)));
+    }
+
+    #[test]
+    fn adds_wider_than_64_bits() {
+        let puzzle = adder(100, 0, 0, &[]);
+        let x = (BigUint::from(1_u32) << 99) + BigUint::from(u64::MAX);
+        let y = (BigUint::from(1_u32) << 99) + BigUint::from(12345_u32);
+
+        let z = puzzle.compute(&[("x", &x), ("y", &y)], "z").unwrap();
+        assert_eq!(z, &x + &y);
+        assert_eq!(z.bits(), 101);
+    }
+
+    #[test]
+    fn rejects_values_wider_than_the_bus() {
+        let mut puzzle = adder(4, 0, 0, &[]);
+        assert_eq!(
+            puzzle.set_bus("x", &BigUint::from(16_u32)),
+            Err(CircuitError::BusOverflow {
+                bus: "x".to_string(),
+                width: 4
+            })
+        );
+    }
+}
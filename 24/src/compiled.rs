@@ -0,0 +1,279 @@
+use num_bigint::BigUint;
+
+use crate::{bus_bit, CircuitError, Operator, Puzzle, WireId, Wires};
+
+/// The number of input vectors that are simulated in one pass, one per bit of a `u64`.
+pub const LANES: usize = 64;
+
+#[derive(Debug, Clone)]
+struct Gate {
+    operator: Operator,
+    /// The operands, unused ones are `0`.
+    operands: [WireId; 2],
+    result: WireId,
+}
+
+/// A netlist prepared for bit-parallel simulation.
+///
+/// Every wire holds a `u64` at the index of its [`WireId`], where bit `i` is the value of the
+/// wire in the `i`-th of [`LANES`] independent simulations. The gates are stored in evaluation
+/// order, so one pass over them evaluates all simulations at once.
+#[derive(Debug, Clone)]
+pub struct CompiledCircuit {
+    wires: Wires,
+    /// The initial wires with their initial values, ordered by name.
+    inputs: Vec<(WireId, u8)>,
+    gates: Vec<Gate>,
+}
+
+impl Operator {
+    /// Applies the operator to all lanes of the operand values at once.
+    fn apply_lanes(&self, a: u64, b: u64) -> u64 {
+        match self {
+            Operator::And => a & b,
+            Operator::Or => a | b,
+            Operator::Xor => a ^ b,
+            Operator::Nand => !(a & b),
+            Operator::Nor => !(a | b),
+            Operator::Xnor => !(a ^ b),
+            Operator::Not => !a,
+            Operator::Zero => 0,
+            Operator::One => u64::MAX,
+        }
+    }
+}
+
+impl Puzzle {
+    /// Compiles the circuit for bit-parallel simulation.
+    pub fn compile(&self) -> Result<CompiledCircuit, CircuitError> {
+        let order = self.evaluation_order()?;
+
+        let mut inputs: Vec<(WireId, u8)> = self
+            .solution
+            .iter()
+            .map(|(wire, value)| (*wire, *value))
+            .collect();
+        inputs.sort_by_key(|(wire, _)| self.name(*wire));
+
+        let gates = order
+            .into_iter()
+            .map(|index| {
+                let sentence = &self.queue[index];
+                let mut operands = [0; 2];
+                for (operand, wire) in operands.iter_mut().zip(&sentence.operands) {
+                    *operand = *wire;
+                }
+                Gate {
+                    operator: sentence.operator,
+                    operands,
+                    result: sentence.result,
+                }
+            })
+            .collect();
+
+        Ok(CompiledCircuit {
+            wires: self.wires.clone(),
+            inputs,
+            gates,
+        })
+    }
+}
+
+impl CompiledCircuit {
+    /// Returns the number of wires, which is the length of the value vectors.
+    pub fn wire_count(&self) -> usize {
+        self.wires.len()
+    }
+
+    /// Returns the id of a wire.
+    pub fn id(&self, wire: &str) -> Option<WireId> {
+        self.wires.id(wire)
+    }
+
+    /// Returns the name of a wire.
+    pub fn name(&self, id: WireId) -> &str {
+        self.wires.name(id)
+    }
+
+    /// Returns the bit indices and ids of all wires of a bus, ordered by bit index.
+    pub fn bus(&self, prefix: &str) -> Vec<(usize, WireId)> {
+        self.wires.bus(prefix)
+    }
+
+    /// Returns the initial wires, ordered by name.
+    pub fn inputs(&self) -> impl Iterator<Item = WireId> + '_ {
+        self.inputs.iter().map(|(id, _)| *id)
+    }
+
+    /// Returns a value vector where every lane holds the initial values of the puzzle.
+    pub fn values(&self) -> Vec<u64> {
+        let mut values = vec![0; self.wires.len()];
+        for (id, value) in &self.inputs {
+            values[*id] = if *value == 1 { u64::MAX } else { 0 };
+        }
+        values
+    }
+
+    /// Evaluates all gates for all lanes. The values of the initial wires have to be set.
+    pub fn evaluate(&self, values: &mut [u64]) {
+        for gate in &self.gates {
+            let [a, b] = gate.operands;
+            values[gate.result] = gate.operator.apply_lanes(values[a], values[b]);
+        }
+    }
+
+    /// Sets the initial wires of a bus in lane `i` to the bits of `lanes[i]`. Lanes beyond
+    /// the given values are left unchanged, more than [`LANES`] values are an error.
+    pub fn set_bus(
+        &self,
+        values: &mut [u64],
+        prefix: &str,
+        lanes: &[BigUint],
+    ) -> Result<(), CircuitError> {
+        if lanes.len() > LANES {
+            return Err(CircuitError::TooManyLanes(lanes.len()));
+        }
+        let wires: Vec<(usize, WireId)> = self
+            .inputs()
+            .filter_map(|id| Some((bus_bit(self.name(id), prefix)?, id)))
+            .collect();
+        let width = wires.iter().map(|(bit, _)| bit + 1).max().unwrap_or(0);
+        if lanes.iter().any(|value| value.bits() > width as u64) {
+            return Err(CircuitError::BusOverflow {
+                bus: prefix.to_string(),
+                width,
+            });
+        }
+
+        for (bit, id) in wires {
+            for (lane, value) in lanes.iter().enumerate() {
+                let mask = 1 << lane;
+                if value.bit(bit as u64) {
+                    values[id] |= mask;
+                } else {
+                    values[id] &= !mask;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the value of a bus in every lane.
+    pub fn read_bus(&self, values: &[u64], prefix: &str) -> Vec<BigUint> {
+        let mut lanes = vec![BigUint::default(); LANES];
+        for (bit, id) in self.bus(prefix) {
+            for (lane, value) in lanes.iter_mut().enumerate() {
+                if values[id] >> lane & 1 == 1 {
+                    value.set_bit(bit as u64, true);
+                }
+            }
+        }
+        lanes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::adder;
+
+    #[test]
+    fn interns_wire_names() {
+        let puzzle = adder(3, 0, 0, &[]);
+        let circuit = puzzle.compile().unwrap();
+
+        assert_eq!(circuit.wire_count(), 6 + 2 * 5 + 2);
+        let id = circuit.id("hs01").unwrap();
+        assert_eq!(circuit.name(id), "hs01");
+        assert_eq!(circuit.id("foo"), None);
+        let bits: Vec<usize> = circuit.bus("z").iter().map(|(bit, _)| *bit).collect();
+        assert_eq!(bits, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn initial_values_fill_every_lane() {
+        let puzzle = adder(5, 13, 22, &[]);
+        let circuit = puzzle.compile().unwrap();
+        let mut values = circuit.values();
+        circuit.evaluate(&mut values);
+
+        let z = circuit.read_bus(&values, "z");
+        assert!(z.iter().all(|value| *value == BigUint::from(35_u32)));
+    }
+
+    #[test]
+    fn simulates_64_vectors_at_once() {
+        let puzzle = adder(8, 0, 0, &[]);
+        let circuit = puzzle.compile().unwrap();
+        let x: Vec<BigUint> = (0..64_u32).map(|i| BigUint::from(i * 3)).collect();
+        let y: Vec<BigUint> = (0..64_u32).map(|i| BigUint::from(255 - i)).collect();
+
+        let mut values = circuit.values();
+        circuit.set_bus(&mut values, "x", &x).unwrap();
+        circuit.set_bus(&mut values, "y", &y).unwrap();
+        circuit.evaluate(&mut values);
+
+        let z = circuit.read_bus(&values, "z");
+        for lane in 0..LANES {
+            assert_eq!(z[lane], &x[lane] + &y[lane], "lane {lane}");
+            assert_eq!(
+                puzzle.compute(&[("x", &x[lane]), ("y", &y[lane])], "z"),
+                Ok(z[lane].clone())
+            );
+        }
+    }
+
+    #[test]
+    fn evaluates_extended_gates_per_lane() {
+        let puzzle: Puzzle = "x00: 0\ny00: 0\n\n1 -> a\nNOT x00 -> b\nb NAND y00 -> c\n\
+            c XNOR a -> z00\nx00 NOR y00 -> z01"
+            .parse()
+            .unwrap();
+        let circuit = puzzle.compile().unwrap();
+        let mut values = circuit.values();
+        let inputs: Vec<BigUint> = (0..4_u32).map(BigUint::from).collect();
+        circuit
+            .set_bus(&mut values, "x", &[0_u32, 1, 0, 1].map(BigUint::from))
+            .unwrap();
+        circuit
+            .set_bus(&mut values, "y", &[0_u32, 0, 1, 1].map(BigUint::from))
+            .unwrap();
+        circuit.evaluate(&mut values);
+
+        let z = circuit.read_bus(&values, "z");
+        for (lane, input) in inputs.iter().enumerate() {
+            let x = BigUint::from(u8::from(input.bit(0)));
+            let y = BigUint::from(u8::from(input.bit(1)));
+            assert_eq!(
+                Ok(z[lane].clone()),
+                puzzle.compute(&[("x", &x), ("y", &y)], "z"),
+                "lane {lane}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_values_wider_than_the_bus() {
+        let circuit = adder(4, 0, 0, &[]).compile().unwrap();
+        let mut values = circuit.values();
+        assert_eq!(
+            circuit.set_bus(&mut values, "x", &[BigUint::from(16_u32)]),
+            Err(CircuitError::BusOverflow {
+                bus: "x".to_string(),
+                width: 4
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_more_values_than_lanes() {
+        let circuit = adder(4, 0, 0, &[]).compile().unwrap();
+        let mut values = circuit.values();
+        let lanes = vec![BigUint::from(1_u32); LANES + 1];
+        assert_eq!(
+            circuit.set_bus(&mut values, "x", &lanes),
+            Err(CircuitError::TooManyLanes(65))
+        );
+        assert_eq!(circuit.set_bus(&mut values, "x", &lanes[1..]), Ok(()));
+    }
+}
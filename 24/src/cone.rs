@@ -0,0 +1,283 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::bus::{split_bus, BUSES};
+use crate::{CircuitError, Puzzle, WireId};
+
+/// The bits of one bus in a set of wires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BusBits {
+    pub bus: String,
+    /// The bit indices, in ascending order.
+    pub bits: Vec<usize>,
+}
+
+/// A set of wires, grouped by the `x`, `y` and `z` buses. A wire belongs to a bus if its
+/// name is the bus followed by a bit index, e.g. `x05`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WireGroups {
+    /// The buses, ordered by name.
+    pub buses: Vec<BusBits>,
+    /// The wires that belong to no bus, ordered by name.
+    pub wires: Vec<String>,
+}
+
+/// The number of gates on the longest path from the initial wires to an output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutputDepth {
+    pub wire: String,
+    pub bit: usize,
+    pub depth: usize,
+}
+
+/// The depths of all outputs of the `z` bus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DepthSummary {
+    /// The outputs, ordered by bit index.
+    pub outputs: Vec<OutputDepth>,
+}
+
+impl DepthSummary {
+    /// Returns the deepest output, the first one if several are equally deep.
+    pub fn deepest(&self) -> Option<&OutputDepth> {
+        self.outputs.iter().rev().max_by_key(|output| output.depth)
+    }
+}
+
+impl<'a> FromIterator<&'a str> for WireGroups {
+    fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
+        let mut groups = WireGroups::default();
+        for wire in iter {
+            match split_bus(wire).filter(|(bus, _)| BUSES.contains(bus)) {
+                Some((bus, bit)) => match groups.buses.iter_mut().find(|b| b.bus == bus) {
+                    Some(bits) => bits.bits.push(bit),
+                    None => groups.buses.push(BusBits {
+                        bus: bus.to_string(),
+                        bits: vec![bit],
+                    }),
+                },
+                None => groups.wires.push(wire.to_string()),
+            }
+        }
+        groups.buses.sort_by(|a, b| a.bus.cmp(&b.bus));
+        for bits in &mut groups.buses {
+            bits.bits.sort_unstable();
+        }
+        groups.wires.sort();
+        groups
+    }
+}
+
+/// Writes the bits of a bus as ranges, e.g. `x: 0-3, 5`.
+impl fmt::Display for BusBits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.bus)?;
+        let mut bits = self.bits.iter().peekable();
+        let mut separator = " ";
+        while let Some(&start) = bits.next() {
+            let mut end = start;
+            while bits.next_if(|bit| **bit == end + 1).is_some() {
+                end += 1;
+            }
+            if start == end {
+                write!(f, "{separator}{start}")?;
+            } else {
+                write!(f, "{separator}{start}-{end}")?;
+            }
+            separator = ", ";
+        }
+        Ok(())
+    }
+}
+
+/// Writes one line per bus, followed by a line with the other wires.
+impl fmt::Display for WireGroups {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bus in &self.buses {
+            writeln!(f, "{bus}")?;
+        }
+        if !self.wires.is_empty() {
+            writeln!(f, "other: {}", self.wires.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes one line per output, followed by the deepest output.
+impl fmt::Display for DepthSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.outputs.iter().map(|o| o.wire.len()).max().unwrap_or(0);
+        for output in &self.outputs {
+            writeln!(f, "{:width$}  {}", output.wire, output.depth)?;
+        }
+        if let Some(deepest) = self.deepest() {
+            writeln!(
+                f,
+                "\nDeepest output: {} with {} gates",
+                deepest.wire, deepest.depth
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Puzzle {
+    /// Returns the wires that `wire` transitively depends on, its cone of influence.
+    pub fn fan_in(&self, wire: &str) -> Result<WireGroups, CircuitError> {
+        let wire = self.wire_id(wire)?;
+        Ok(self.groups(&self.fan_in_wires(wire), wire))
+    }
+
+    /// Returns the wires that transitively depend on `wire`.
+    pub fn fan_out(&self, wire: &str) -> Result<WireGroups, CircuitError> {
+        let wire = self.wire_id(wire)?;
+        let mut consumers: Vec<Vec<WireId>> = vec![Vec::new(); self.wires.len()];
+        for sentence in &self.queue {
+            for operand in &sentence.operands {
+                consumers[*operand].push(sentence.result);
+            }
+        }
+
+        let mut reached = vec![false; self.wires.len()];
+        reached[wire] = true;
+        let mut stack = vec![wire];
+        while let Some(wire) = stack.pop() {
+            for consumer in &consumers[wire] {
+                if !reached[*consumer] {
+                    reached[*consumer] = true;
+                    stack.push(*consumer);
+                }
+            }
+        }
+        Ok(self.groups(&reached, wire))
+    }
+
+    /// Returns the number of gates on the longest path to every output of the `z` bus.
+    pub fn output_depths(&self) -> Result<DepthSummary, CircuitError> {
+        let mut depths = vec![0; self.wires.len()];
+        for index in self.evaluation_order()? {
+            let sentence = &self.queue[index];
+            depths[sentence.result] = 1 + sentence
+                .operands
+                .iter()
+                .map(|w| depths[*w])
+                .max()
+                .unwrap_or(0);
+        }
+
+        let outputs = self
+            .wires
+            .bus("z")
+            .into_iter()
+            .map(|(bit, id)| OutputDepth {
+                wire: self.name(id).to_string(),
+                bit,
+                depth: depths[id],
+            })
+            .collect();
+        Ok(DepthSummary { outputs })
+    }
+
+    /// Marks `wire` and the wires it transitively depends on, indexed by [`WireId`].
+    pub(crate) fn fan_in_wires(&self, wire: WireId) -> Vec<bool> {
+        let mut drivers: Vec<Option<usize>> = vec![None; self.wires.len()];
+        for (index, sentence) in self.queue.iter().enumerate() {
+            drivers[sentence.result] = Some(index);
+        }
+
+        let mut reached = vec![false; self.wires.len()];
+        reached[wire] = true;
+        let mut stack = vec![wire];
+        while let Some(wire) = stack.pop() {
+            let Some(index) = drivers[wire] else {
+                continue;
+            };
+            for operand in &self.queue[index].operands {
+                if !reached[*operand] {
+                    reached[*operand] = true;
+                    stack.push(*operand);
+                }
+            }
+        }
+        reached
+    }
+
+    /// Returns the id of a known wire.
+    pub(crate) fn wire_id(&self, wire: &str) -> Result<WireId, CircuitError> {
+        self.wires
+            .id(wire)
+            .ok_or_else(|| CircuitError::UnknownWire(wire.to_string()))
+    }
+
+    /// Groups the marked wires except `start`.
+    fn groups(&self, reached: &[bool], start: WireId) -> WireGroups {
+        self.wires
+            .iter()
+            .filter(|(id, _)| reached[*id] && *id != start)
+            .map(|(_, name)| name)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::adder;
+
+    #[test]
+    fn fan_in_of_an_output_is_the_lower_input_bits() {
+        let puzzle = adder(6, 0, 0, &[]);
+        let fan_in = puzzle.fan_in("z03").unwrap();
+
+        let buses: Vec<(&str, &[usize])> = fan_in
+            .buses
+            .iter()
+            .map(|b| (b.bus.as_str(), b.bits.as_slice()))
+            .collect();
+        assert_eq!(
+            buses,
+            vec![("x", &[0, 1, 2, 3][..]), ("y", &[0, 1, 2, 3][..])]
+        );
+        assert_eq!(
+            fan_in.wires,
+            ["cd01", "cd02", "co00", "co01", "co02", "hc01", "hc02", "hs01", "hs02", "hs03"]
+        );
+        assert_eq!(
+            puzzle.fan_in("q"),
+            Err(CircuitError::UnknownWire("q".to_string()))
+        );
+    }
+
+    #[test]
+    fn fan_out_of_an_input_is_the_higher_outputs() {
+        let puzzle = adder(6, 0, 0, &[]);
+        let fan_out = puzzle.fan_out("y02").unwrap();
+
+        let z = fan_out.buses.iter().find(|b| b.bus == "z").unwrap();
+        assert_eq!(z.bits, vec![2, 3, 4, 5, 6]);
+        assert_eq!(z.to_string(), "z: 2-6");
+        assert!(fan_out.buses.iter().all(|b| b.bus != "x" && b.bus != "y"));
+    }
+
+    #[test]
+    fn groups_wires_without_bit_index_separately() {
+        let puzzle: Puzzle = "x00: 1\nx02: 0\n\nx00 AND x02 -> abc\nNOT abc -> z00"
+            .parse()
+            .unwrap();
+        let fan_in = puzzle.fan_in("z00").unwrap();
+
+        assert_eq!(fan_in.wires, vec!["abc"]);
+        assert_eq!(fan_in.to_string(), "x: 0, 2\nother: abc\n");
+    }
+
+    #[test]
+    fn summarizes_output_depths() {
+        let summary = adder(4, 0, 0, &[]).output_depths().unwrap();
+
+        let depths: Vec<usize> = summary.outputs.iter().map(|o| o.depth).collect();
+        assert_eq!(depths, vec![1, 2, 4, 6, 7]);
+        let deepest = summary.deepest().unwrap();
+        assert_eq!((deepest.wire.as_str(), deepest.depth), ("z04", 7));
+    }
+}
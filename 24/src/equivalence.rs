@@ -0,0 +1,479 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use num_bigint::BigUint;
+
+use crate::bdd::{Bdd, FALSE};
+use crate::bus::{bus_value_by_name, split_bus};
+use crate::compiled::LANES;
+use crate::{CircuitError, Operator, Puzzle};
+
+/// Circuits with at most this many initial wires are compared by simulating every input.
+/// Wider circuits are compared symbolically.
+const EXHAUSTIVE_INPUTS: usize = 16;
+
+/// Operations on the `x` and `y` buses that a circuit can be checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusOperation {
+    Add,
+    And,
+    Or,
+    Xor,
+}
+
+/// The result of comparing the `z` outputs of two circuits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Equivalence {
+    Equivalent,
+    Different(Counterexample),
+}
+
+/// An input for which two circuits compute different `z` outputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counterexample {
+    /// The values of all initial wires.
+    pub inputs: HashMap<String, u8>,
+    /// The output of the checked circuit.
+    pub actual: BigUint,
+    /// The output of the reference.
+    pub expected: BigUint,
+}
+
+impl Counterexample {
+    /// Returns the index of the lowest output bit that differs.
+    pub fn bit(&self) -> usize {
+        (&self.actual ^ &self.expected)
+            .trailing_zeros()
+            .unwrap_or_default() as usize
+    }
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "x = {}, y = {} gives z = {} instead of {}, first difference in z{:02}",
+            bus_value_by_name(&self.inputs, "x"),
+            bus_value_by_name(&self.inputs, "y"),
+            self.actual,
+            self.expected,
+            self.bit()
+        )
+    }
+}
+
+impl BusOperation {
+    pub fn apply(&self, x: &BigUint, y: &BigUint) -> BigUint {
+        match self {
+            BusOperation::Add => x + y,
+            BusOperation::And => x & y,
+            BusOperation::Or => x | y,
+            BusOperation::Xor => x ^ y,
+        }
+    }
+
+    /// Builds a circuit with the same initial wires as `circuit` that computes the operation
+    /// on its `x` and `y` buses. Missing bits of a narrower bus are read as `0`.
+    fn reference_circuit(&self, circuit: &Puzzle) -> Puzzle {
+        let width = circuit.bus_width("x").max(circuit.bus_width("y"));
+
+        let mut reference = Puzzle::default();
+        for (wire, value) in &circuit.solution {
+            reference.set_initial(circuit.name(*wire), *value);
+        }
+
+        // the internal wires of the reference get a prefix that no wire of the circuit has
+        let mut prefix = "ref".to_string();
+        while circuit
+            .wires
+            .iter()
+            .any(|(_, wire)| wire.starts_with(&prefix))
+        {
+            prefix.push('_');
+        }
+        let zero = format!("{prefix}zero");
+        reference.add_gate(Operator::Zero, &[], &zero);
+
+        let inputs = |bus: &str| {
+            let mut bits = vec![zero.clone(); width];
+            for (bit, wire) in circuit.wires.bus(bus) {
+                if circuit.solution.contains_key(&wire) {
+                    bits[bit] = circuit.name(wire).to_string();
+                }
+            }
+            bits
+        };
+        let (xs, ys) = (inputs("x"), inputs("y"));
+
+        let mut carry = zero.clone();
+        for bit in 0..width {
+            let (x, y) = (&xs[bit], &ys[bit]);
+            let z = format!("z{bit:02}");
+            let operator = match self {
+                BusOperation::Add => {
+                    let half_sum = format!("{prefix}hs{bit:02}");
+                    let half_carry = format!("{prefix}hc{bit:02}");
+                    let carry_detail = format!("{prefix}cd{bit:02}");
+                    let carry_out = if bit + 1 == width {
+                        format!("z{width:02}")
+                    } else {
+                        format!("{prefix}co{bit:02}")
+                    };
+                    reference.add_gate(Operator::Xor, &[x, y], &half_sum);
+                    reference.add_gate(Operator::And, &[x, y], &half_carry);
+                    reference.add_gate(Operator::Xor, &[&half_sum, &carry], &z);
+                    reference.add_gate(Operator::And, &[&half_sum, &carry], &carry_detail);
+                    reference.add_gate(Operator::Or, &[&half_carry, &carry_detail], &carry_out);
+                    carry = carry_out;
+                    continue;
+                }
+                BusOperation::And => Operator::And,
+                BusOperation::Or => Operator::Or,
+                BusOperation::Xor => Operator::Xor,
+            };
+            reference.add_gate(operator, &[x, y], &z);
+        }
+
+        reference
+    }
+}
+
+/// Orders wires by bit index first, so the bits of different buses are interleaved.
+/// This keeps the decision diagrams of adders small.
+fn input_order(wire: &str) -> (usize, &str) {
+    (split_bus(wire).map_or(usize::MAX, |(_, bit)| bit), wire)
+}
+
+impl Puzzle {
+    /// Checks whether the circuit computes `operation` on its `x` and `y` buses for every
+    /// value of the initial wires.
+    pub fn computes(&self, operation: BusOperation) -> Result<Equivalence, CircuitError> {
+        self.equivalent_to(&operation.reference_circuit(self))
+    }
+
+    /// Checks whether both circuits compute the same `z` outputs for every value of the
+    /// initial wires, which have to be the same in both. Missing output bits count as `0`.
+    pub fn equivalent_to(&self, reference: &Puzzle) -> Result<Equivalence, CircuitError> {
+        let exhaustive = self.solution.len() <= EXHAUSTIVE_INPUTS;
+        self.compare(reference, exhaustive)
+    }
+
+    fn compare(&self, reference: &Puzzle, exhaustive: bool) -> Result<Equivalence, CircuitError> {
+        let mut inputs: Vec<&str> = self.solution.keys().map(|w| self.name(*w)).collect();
+        let mut reference_inputs: Vec<&str> = reference
+            .solution
+            .keys()
+            .map(|w| reference.name(*w))
+            .collect();
+        inputs.sort_by_key(|wire| input_order(wire));
+        reference_inputs.sort_by_key(|wire| input_order(wire));
+
+        if inputs != reference_inputs {
+            let mut mismatched: Vec<String> = inputs
+                .iter()
+                .filter(|wire| !reference_inputs.contains(wire))
+                .chain(
+                    reference_inputs
+                        .iter()
+                        .filter(|wire| !inputs.contains(wire)),
+                )
+                .map(|wire| wire.to_string())
+                .collect();
+            mismatched.sort();
+            return Err(CircuitError::InputMismatch(mismatched));
+        }
+
+        let counterexample = if exhaustive {
+            self.compare_exhaustive(reference, &inputs)?
+        } else {
+            self.compare_symbolic(reference, &inputs)?
+        };
+        Ok(match counterexample {
+            Some(counterexample) => Equivalence::Different(counterexample),
+            None => Equivalence::Equivalent,
+        })
+    }
+
+    /// Simulates both circuits for every assignment of the inputs, [`LANES`] assignments
+    /// per pass.
+    fn compare_exhaustive(
+        &self,
+        reference: &Puzzle,
+        inputs: &[&str],
+    ) -> Result<Option<Counterexample>, CircuitError> {
+        let circuits = [self.compile()?, reference.compile()?];
+        let mut values = circuits.clone().map(|circuit| circuit.values());
+
+        // every initial wire is interned by `compile`
+        let input_ids = circuits.each_ref().map(|circuit| {
+            inputs
+                .iter()
+                .map(|wire| circuit.id(wire).unwrap_or_default())
+                .collect::<Vec<usize>>()
+        });
+        let mut outputs: BTreeMap<usize, [Option<usize>; 2]> = BTreeMap::new();
+        for (index, circuit) in circuits.iter().enumerate() {
+            for (bit, id) in circuit.bus("z") {
+                outputs.entry(bit).or_default()[index] = Some(id);
+            }
+        }
+
+        let total = 1_u64 << inputs.len();
+        for base in (0..total).step_by(LANES) {
+            for index in 0..inputs.len() {
+                let lanes = (0..LANES as u64)
+                    .filter(|lane| (base + lane) >> index & 1 == 1)
+                    .fold(0, |lanes, lane| lanes | 1 << lane);
+                for (circuit, ids) in input_ids.iter().enumerate() {
+                    values[circuit][ids[index]] = lanes;
+                }
+            }
+            for (circuit, values) in circuits.iter().zip(values.iter_mut()) {
+                circuit.evaluate(values);
+            }
+
+            let output = |circuit: usize, id: Option<usize>| id.map_or(0, |id| values[circuit][id]);
+            let mut difference = outputs.values().fold(0, |difference, [a, b]| {
+                difference | (output(0, *a) ^ output(1, *b))
+            });
+            if total - base < LANES as u64 {
+                difference &= (1 << (total - base)) - 1;
+            }
+
+            if difference != 0 {
+                let lane = difference.trailing_zeros() as usize;
+                let assignment = base + lane as u64;
+                let [actual, expected] = [0, 1].map(|index| {
+                    circuits[index]
+                        .read_bus(&values[index], "z")
+                        .swap_remove(lane)
+                });
+                return Ok(Some(Counterexample {
+                    inputs: inputs
+                        .iter()
+                        .enumerate()
+                        .map(|(index, wire)| (wire.to_string(), (assignment >> index & 1) as u8))
+                        .collect(),
+                    actual,
+                    expected,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Builds decision diagrams of the outputs of both circuits and compares them bit by bit,
+    /// starting at the lowest bit.
+    fn compare_symbolic(
+        &self,
+        reference: &Puzzle,
+        inputs: &[&str],
+    ) -> Result<Option<Counterexample>, CircuitError> {
+        let mut bdd = Bdd::new();
+        let mut variables = HashMap::with_capacity(inputs.len());
+        for (index, wire) in inputs.iter().enumerate() {
+            variables.insert(*wire, bdd.variable(index)?);
+        }
+
+        let actual = self.output_functions(&mut bdd, &variables)?;
+        let expected = reference.output_functions(&mut bdd, &variables)?;
+
+        let mut bits: Vec<usize> = actual.keys().chain(expected.keys()).copied().collect();
+        bits.sort();
+        bits.dedup();
+        for bit in bits {
+            let output =
+                |functions: &BTreeMap<usize, usize>| functions.get(&bit).copied().unwrap_or(FALSE);
+            let difference = bdd.difference(output(&actual), output(&expected))?;
+            let Some(assignment) = bdd.satisfying_assignment(difference) else {
+                continue;
+            };
+
+            let mut values: HashMap<String, u8> =
+                inputs.iter().map(|wire| (wire.to_string(), 0)).collect();
+            for (variable, value) in assignment {
+                values.insert(inputs[variable].to_string(), u8::from(value));
+            }
+            let simulate = |puzzle: &Puzzle| -> Result<BigUint, CircuitError> {
+                let mut puzzle = puzzle.clone();
+                for (wire, value) in &values {
+                    puzzle.set_initial(wire, *value);
+                }
+                puzzle.read_bus("z")
+            };
+            return Ok(Some(Counterexample {
+                actual: simulate(self)?,
+                expected: simulate(reference)?,
+                inputs: values,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Returns the decision diagrams of the `z` outputs, keyed by bit index.
+    fn output_functions(
+        &self,
+        bdd: &mut Bdd,
+        variables: &HashMap<&str, usize>,
+    ) -> Result<BTreeMap<usize, usize>, CircuitError> {
+        let mut functions: Vec<usize> = vec![FALSE; self.wires.len()];
+        for wire in self.solution.keys() {
+            functions[*wire] = variables[self.name(*wire)];
+        }
+        for index in self.evaluation_order()? {
+            let sentence = &self.queue[index];
+            let operands: Vec<usize> = sentence.operands.iter().map(|w| functions[*w]).collect();
+            functions[sentence.result] = bdd.gate(&sentence.operator, &operands)?;
+        }
+
+        Ok(self
+            .wires
+            .bus("z")
+            .into_iter()
+            .map(|(bit, wire)| (bit, functions[wire]))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::adder;
+
+    /// A half adder and the same half adder built from NAND gates only.
+    const HALF_ADDER: &str = "x00: 0\ny00: 0\n\nx00 XOR y00 -> z00\nx00 AND y00 -> z01";
+    const NAND_HALF_ADDER: &str = "x00: 0\ny00: 0\n\nx00 NAND y00 -> n\nx00 NAND n -> a\n\
+        y00 NAND n -> b\na NAND b -> z00\nn NAND n -> z01";
+
+    fn check(puzzle: &Puzzle, reference: &Puzzle) -> [Equivalence; 2] {
+        [true, false].map(|exhaustive| puzzle.compare(reference, exhaustive).unwrap())
+    }
+
+    #[test]
+    fn compares_netlists() {
+        let half_adder: Puzzle = HALF_ADDER.parse().unwrap();
+        let nand_half_adder: Puzzle = NAND_HALF_ADDER.parse().unwrap();
+        let [exhaustive, symbolic] = check(&nand_half_adder, &half_adder);
+        assert_eq!(exhaustive, Equivalence::Equivalent);
+        assert_eq!(symbolic, Equivalence::Equivalent);
+
+        let broken: Puzzle = NAND_HALF_ADDER
+            .replace("n NAND n", "a NAND b")
+            .parse()
+            .unwrap();
+        for result in check(&broken, &half_adder) {
+            let Equivalence::Different(counterexample) = result else {
+                panic!("circuits should differ");
+            };
+            assert_eq!(counterexample.bit(), 1);
+            assert_ne!(counterexample.actual, counterexample.expected);
+        }
+    }
+
+    #[test]
+    fn small_adder_computes_sum() {
+        let puzzle = adder(5, 0, 0, &[]);
+        let reference = BusOperation::Add.reference_circuit(&puzzle);
+        for result in check(&puzzle, &reference) {
+            assert_eq!(result, Equivalence::Equivalent);
+        }
+        assert!(matches!(
+            puzzle.computes(BusOperation::Xor),
+            Ok(Equivalence::Different(_))
+        ));
+    }
+
+    #[test]
+    fn small_adder_with_swap_has_counterexample() {
+        let puzzle = adder(5, 0, 0, &[("z02", "co02")]);
+        let reference = BusOperation::Add.reference_circuit(&puzzle);
+
+        for result in check(&puzzle, &reference) {
+            let Equivalence::Different(counterexample) = result else {
+                panic!("swapped adder should not add");
+            };
+            let x = bus_value_by_name(&counterexample.inputs, "x");
+            let y = bus_value_by_name(&counterexample.inputs, "y");
+            assert_eq!(counterexample.expected, &x + &y);
+            assert_eq!(
+                puzzle.compute(&[("x", &x), ("y", &y)], "z"),
+                Ok(counterexample.actual.clone())
+            );
+            assert_eq!(counterexample.bit(), 2);
+        }
+    }
+
+    #[test]
+    fn wide_adder_is_checked_symbolically() {
+        let puzzle = adder(45, 0, 0, &[]);
+        assert_eq!(
+            puzzle.computes(BusOperation::Add),
+            Ok(Equivalence::Equivalent)
+        );
+
+        let puzzle = adder(45, 0, 0, &[("z20", "co20")]);
+        let Ok(Equivalence::Different(counterexample)) = puzzle.computes(BusOperation::Add) else {
+            panic!("swapped adder should not add");
+        };
+        let x = bus_value_by_name(&counterexample.inputs, "x");
+        let y = bus_value_by_name(&counterexample.inputs, "y");
+        assert_eq!(counterexample.expected, &x + &y);
+        assert_ne!(counterexample.actual, counterexample.expected);
+        assert_eq!(counterexample.bit(), 20);
+        assert!(counterexample
+            .to_string()
+            .ends_with(", first difference in z20"));
+    }
+
+    #[test]
+    fn bitwise_operations() {
+        let puzzle: Puzzle =
+            "x00: 0\nx01: 0\ny00: 0\n\n0 -> c\nx00 AND y00 -> z00\nx01 AND c -> z01"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            puzzle.computes(BusOperation::And),
+            Ok(Equivalence::Equivalent)
+        );
+        assert!(matches!(
+            puzzle.computes(BusOperation::Or),
+            Ok(Equivalence::Different(_))
+        ));
+    }
+
+    #[test]
+    fn reads_inputs_without_padding() {
+        let puzzle: Puzzle = "x0: 0\nx1: 0\ny0: 0\ny1: 0\n\nx0 XOR y0 -> z00\nx1 XOR y1 -> z01"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            puzzle.computes(BusOperation::Xor),
+            Ok(Equivalence::Equivalent)
+        );
+    }
+
+    #[test]
+    fn reference_wires_do_not_collide() {
+        let puzzle: Puzzle = "refzero: 1\nrefhs00: 1\nx00: 0\ny00: 0\n\n\
+            x00 XOR y00 -> z00\nx00 AND y00 -> z01"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            puzzle.computes(BusOperation::Add),
+            Ok(Equivalence::Equivalent)
+        );
+    }
+
+    #[test]
+    fn rejects_different_inputs() {
+        let half_adder: Puzzle = HALF_ADDER.parse().unwrap();
+        let other: Puzzle = "x00: 0\nx01: 0\n\nx00 XOR x01 -> z00".parse().unwrap();
+        assert_eq!(
+            half_adder.equivalent_to(&other),
+            Err(CircuitError::InputMismatch(vec![
+                "x01".to_string(),
+                "y00".to_string()
+            ]))
+        );
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::bus::BUSES;
+use crate::{bus_bit, Operator, Puzzle, WireId};
+
+/// Graph formats the circuit can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+impl Operator {
+    fn dot_shape(&self) -> &'static str {
+        match self {
+            Operator::And => "box",
+            Operator::Or => "ellipse",
+            Operator::Xor => "diamond",
+            Operator::Nand => "octagon",
+            Operator::Nor => "invtriangle",
+            Operator::Xnor => "hexagon",
+            Operator::Not => "triangle",
+            Operator::Zero | Operator::One => "plaintext",
+        }
+    }
+
+    /// Returns the opening and closing brackets of the Mermaid node shape.
+    fn mermaid_shape(&self) -> (&'static str, &'static str) {
+        match self {
+            Operator::And => ("[", "]"),
+            Operator::Or => ("(", ")"),
+            Operator::Xor => ("{{", "}}"),
+            Operator::Nand => ("[[", "]]"),
+            Operator::Nor => ("([", "])"),
+            Operator::Xnor => ("{", "}"),
+            Operator::Not => (">", "]"),
+            Operator::Zero | Operator::One => ("[/", "/]"),
+        }
+    }
+}
+
+/// The nodes and edges of the circuit, shared by all export formats.
+///
+/// The wires of the `x`, `y` and `z` buses are grouped into clusters.
+/// Every initial wire, every operand without a driver and every output bus wire becomes a
+/// wire node. Every gate becomes a node named after the wire it drives.
+struct Graph<'a> {
+    /// Wire nodes grouped by bus, other wire nodes are listed under the empty bus.
+    wires: Vec<(&'static str, Vec<&'a str>)>,
+    gates: Vec<(&'a str, &'a Operator)>,
+    /// Edges between node ids.
+    edges: Vec<(String, String)>,
+    highlighted: HashSet<String>,
+}
+
+fn wire_id(wire: &str) -> String {
+    format!("w_{wire}")
+}
+
+fn gate_id(wire: &str) -> String {
+    format!("g_{wire}")
+}
+
+impl Puzzle {
+    /// Exports the gate network as a graph. If `highlight` is set, all nodes and edges the
+    /// output bit with this index depends on are highlighted.
+    pub fn export_graph(&self, format: GraphFormat, highlight: Option<usize>) -> String {
+        let graph = self.graph(highlight);
+        match format {
+            GraphFormat::Dot => graph.to_dot(),
+            GraphFormat::Mermaid => graph.to_mermaid(),
+        }
+    }
+
+    fn graph(&self, highlight: Option<usize>) -> Graph<'_> {
+        let driven: HashSet<WireId> = self.queue.iter().map(|s| s.result).collect();
+        // operands without a driver are drawn as wire nodes like the initial wires
+        let mut sources: HashSet<WireId> = self.solution.keys().copied().collect();
+        sources.extend(
+            self.queue
+                .iter()
+                .flat_map(|s| &s.operands)
+                .filter(|operand| !driven.contains(operand)),
+        );
+
+        let mut initial: Vec<&str> = sources.iter().map(|w| self.name(*w)).collect();
+        let mut outputs: Vec<&str> = self
+            .queue
+            .iter()
+            .map(|s| self.name(s.result))
+            .filter(|wire| bus_bit(wire, "z").is_some())
+            .collect();
+        initial.sort();
+        outputs.sort();
+
+        let mut wires: Vec<(&'static str, Vec<&str>)> = Vec::new();
+        for bus in BUSES {
+            let members: Vec<&str> = initial
+                .iter()
+                .chain(outputs.iter())
+                .copied()
+                .filter(|wire| bus_bit(wire, bus).is_some())
+                .collect();
+            if !members.is_empty() {
+                wires.push((bus, members));
+            }
+        }
+        let other: Vec<&str> = initial
+            .iter()
+            .copied()
+            .filter(|wire| BUSES.iter().all(|bus| bus_bit(wire, bus).is_none()))
+            .collect();
+        wires.push(("", other));
+
+        let mut edges = Vec::new();
+        for sentence in &self.queue {
+            for operand in &sentence.operands {
+                let name = self.name(*operand);
+                let source = if sources.contains(operand) {
+                    wire_id(name)
+                } else {
+                    gate_id(name)
+                };
+                edges.push((source, gate_id(self.name(sentence.result))));
+            }
+        }
+        for output in &outputs {
+            edges.push((gate_id(output), wire_id(output)));
+        }
+
+        let highlighted = highlight
+            .and_then(|bit| self.wires.bus("z").into_iter().find(|(b, _)| *b == bit))
+            .filter(|(_, wire)| self.queue.iter().any(|s| s.result == *wire))
+            .map(|(_, output)| self.highlighted_nodes(output))
+            .unwrap_or_default();
+
+        Graph {
+            wires,
+            gates: self
+                .queue
+                .iter()
+                .map(|s| (self.name(s.result), &s.operator))
+                .collect(),
+            edges,
+            highlighted,
+        }
+    }
+
+    /// Returns the ids of all nodes the output wire depends on, including its own node.
+    fn highlighted_nodes(&self, output: WireId) -> HashSet<String> {
+        let mut drivers: Vec<Option<&[WireId]>> = vec![None; self.wires.len()];
+        for sentence in &self.queue {
+            drivers[sentence.result] = Some(&sentence.operands);
+        }
+
+        let mut nodes = HashSet::from([wire_id(self.name(output))]);
+        let mut stack = vec![output];
+        while let Some(wire) = stack.pop() {
+            match drivers[wire] {
+                Some(operands) => {
+                    if nodes.insert(gate_id(self.name(wire))) {
+                        stack.extend(operands);
+                    }
+                }
+                None => {
+                    nodes.insert(wire_id(self.name(wire)));
+                }
+            }
+        }
+        nodes
+    }
+}
+
+impl Graph<'_> {
+    fn is_highlighted(&self, from: &str, to: &str) -> bool {
+        self.highlighted.contains(from) && self.highlighted.contains(to)
+    }
+
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n");
+        let highlight = |id: &str| {
+            if self.highlighted.contains(id) {
+                ", color=red, penwidth=2"
+            } else {
+                ""
+            }
+        };
+
+        for (bus, wires) in &self.wires {
+            let indent = if bus.is_empty() {
+                "    "
+            } else {
+                let _ = writeln!(
+                    dot,
+                    "    subgraph cluster_{bus} {{\n        label=\"{bus}\";"
+                );
+                "        "
+            };
+            for wire in wires {
+                let id = wire_id(wire);
+                let _ = writeln!(
+                    dot,
+                    "{indent}{id} [label=\"{wire}\", shape=circle{}];",
+                    highlight(&id)
+                );
+            }
+            if !bus.is_empty() {
+                dot.push_str("    }\n");
+            }
+        }
+
+        for (wire, operator) in &self.gates {
+            let id = gate_id(wire);
+            let _ = writeln!(
+                dot,
+                "    {id} [label=\"{}\\n{wire}\", shape={}{}];",
+                operator,
+                operator.dot_shape(),
+                highlight(&id)
+            );
+        }
+
+        for (from, to) in &self.edges {
+            let style = if self.is_highlighted(from, to) {
+                " [color=red, penwidth=2]"
+            } else {
+                ""
+            };
+            let _ = writeln!(dot, "    {from} -> {to}{style};");
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("flowchart LR\n");
+
+        for (bus, wires) in &self.wires {
+            let indent = if bus.is_empty() {
+                "    "
+            } else {
+                let _ = writeln!(mermaid, "    subgraph bus_{bus} [{bus}]");
+                "        "
+            };
+            for wire in wires {
+                let _ = writeln!(mermaid, "{indent}{}(({wire}))", wire_id(wire));
+            }
+            if !bus.is_empty() {
+                mermaid.push_str("    end\n");
+            }
+        }
+
+        for (wire, operator) in &self.gates {
+            let (open, close) = operator.mermaid_shape();
+            let _ = writeln!(
+                mermaid,
+                "    {}{open}\"{} {wire}\"{close}",
+                gate_id(wire),
+                operator
+            );
+        }
+
+        let mut highlighted_edges = Vec::new();
+        for (index, (from, to)) in self.edges.iter().enumerate() {
+            let _ = writeln!(mermaid, "    {from} --> {to}");
+            if self.is_highlighted(from, to) {
+                highlighted_edges.push(index.to_string());
+            }
+        }
+
+        if !self.highlighted.is_empty() {
+            let mut nodes: Vec<&str> = self.highlighted.iter().map(String::as_str).collect();
+            nodes.sort();
+            let _ = writeln!(
+                mermaid,
+                "    classDef highlight stroke:#d00,stroke-width:3px\n    class {} highlight",
+                nodes.join(",")
+            );
+            let _ = writeln!(
+                mermaid,
+                "    linkStyle {} stroke:#d00,stroke-width:3px",
+                highlighted_edges.join(",")
+            );
+        }
+
+        mermaid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CIRCUIT: &str = "x00: 1\nx01: 0\ny00: 1\ny01: 1\n\n\
+        x00 XOR y00 -> z00\nx00 AND y00 -> c00\nx01 XOR y01 -> s01\n\
+        s01 XOR c00 -> z01\nx01 AND y01 -> c01";
+
+    #[test]
+    fn exports_dot() {
+        let puzzle: Puzzle = CIRCUIT.parse().unwrap();
+        let dot = puzzle.export_graph(GraphFormat::Dot, None);
+
+        assert!(dot.starts_with("digraph circuit {\n"));
+        assert!(dot.contains(
+            "    subgraph cluster_x {\n        label=\"x\";\n        w_x00 [label=\"x00\", shape=circle];\n"
+        ));
+        assert!(dot.contains("    g_c00 [label=\"AND\\nc00\", shape=box];\n"));
+        assert!(dot.contains("    g_z01 [label=\"XOR\\nz01\", shape=diamond];\n"));
+        assert!(dot.contains("    w_x00 -> g_z00;\n"));
+        assert!(dot.contains("    g_c00 -> g_z01;\n"));
+        assert!(dot.contains("    g_z01 -> w_z01;\n"));
+        assert!(!dot.contains("red"));
+    }
+
+    #[test]
+    fn exports_mermaid() {
+        let puzzle: Puzzle = CIRCUIT.parse().unwrap();
+        let mermaid = puzzle.export_graph(GraphFormat::Mermaid, None);
+
+        assert!(mermaid.starts_with("flowchart LR\n    subgraph bus_x [x]\n"));
+        assert!(mermaid.contains("        w_y01((y01))\n"));
+        assert!(mermaid.contains("    g_c00[\"AND c00\"]\n"));
+        assert!(mermaid.contains("    g_z00{{\"XOR z00\"}}\n"));
+        assert!(mermaid.contains("    w_x00 --> g_z00\n"));
+        assert!(!mermaid.contains("classDef"));
+    }
+
+    #[test]
+    fn highlights_output_bit() {
+        let puzzle: Puzzle = CIRCUIT.parse().unwrap();
+
+        let dot = puzzle.export_graph(GraphFormat::Dot, Some(1));
+        assert!(
+            dot.contains("    g_c00 [label=\"AND\\nc00\", shape=box, color=red, penwidth=2];\n")
+        );
+        assert!(dot.contains("    g_c00 -> g_z01 [color=red, penwidth=2];\n"));
+        assert!(dot.contains("    g_c01 [label=\"AND\\nc01\", shape=box];\n"));
+        assert!(dot.contains("    g_z00 -> w_z00;\n"));
+
+        let mermaid = puzzle.export_graph(GraphFormat::Mermaid, Some(1));
+        assert!(mermaid
+            .contains("    class g_c00,g_s01,g_z01,w_x00,w_x01,w_y00,w_y01,w_z01 highlight\n"));
+    }
+
+    #[test]
+    fn draws_undriven_operands_as_wires() {
+        let puzzle: Puzzle = "x00: 1\n\nx00 AND q -> z00".parse().unwrap();
+
+        let dot = puzzle.export_graph(GraphFormat::Dot, None);
+        assert!(dot.contains("\n    w_q [label=\"q\", shape=circle];\n"));
+        assert!(dot.contains("    w_q -> g_z00;\n"));
+        assert!(!dot.contains("g_q"));
+
+        let mermaid = puzzle.export_graph(GraphFormat::Mermaid, None);
+        assert!(mermaid.contains("\n    w_q((q))\n"));
+        assert!(mermaid.contains("    w_q --> g_z00\n"));
+        assert!(!mermaid.contains("g_q"));
+    }
+
+    #[test]
+    fn skips_empty_buses() {
+        let puzzle: Puzzle = "x00: 1\n\nNOT x00 -> z00".parse().unwrap();
+
+        let dot = puzzle.export_graph(GraphFormat::Dot, None);
+        assert!(dot.contains("subgraph cluster_x"));
+        assert!(!dot.contains("cluster_y"));
+
+        let mermaid = puzzle.export_graph(GraphFormat::Mermaid, None);
+        assert!(mermaid.contains("subgraph bus_z"));
+        assert!(!mermaid.contains("bus_y"));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{Operator, Puzzle};
+
+impl Puzzle {
+    /// Builds a ripple-carry adder of `x` and `y` buses with `bits` bits each, with all
+    /// inputs set to `0`, and swaps the outputs of the gates driving each listed pair of wires.
+    ///
+    /// Bit 0 is `x XOR y -> z00` and `x AND y -> co00`. Every higher bit is built as
+    /// `x XOR y -> hs`, `x AND y -> hc`, `hs XOR carry -> z`, `hs AND carry -> cd` and
+    /// `hc OR cd -> co`, where the carry of the last bit drives the highest output bit.
+    ///
+    /// This builds fixtures for tests and benchmarks and is not part of the supported API.
+    #[doc(hidden)]
+    pub fn ripple_carry_adder(bits: usize, swaps: &[(&str, &str)]) -> Puzzle {
+        let swapped = |wire: String| {
+            swaps
+                .iter()
+                .find_map(|&(a, b)| match wire.as_str() {
+                    w if w == a => Some(b.to_string()),
+                    w if w == b => Some(a.to_string()),
+                    _ => None,
+                })
+                .unwrap_or(wire)
+        };
+
+        let mut puzzle = Puzzle::default();
+        for bus in ["x", "y"] {
+            for bit in 0..bits {
+                puzzle.set_initial(&format!("{bus}{bit:02}"), 0);
+            }
+        }
+        if bits == 0 {
+            return puzzle;
+        }
+
+        puzzle.add_gate(Operator::Xor, &["x00", "y00"], &swapped("z00".to_string()));
+        puzzle.add_gate(Operator::And, &["x00", "y00"], &swapped("co00".to_string()));
+        for bit in 1..bits {
+            let (x, y) = (format!("x{bit:02}"), format!("y{bit:02}"));
+            let (hs, hc, cd) = (
+                format!("hs{bit:02}"),
+                format!("hc{bit:02}"),
+                format!("cd{bit:02}"),
+            );
+            let carry_in = format!("co{:02}", bit - 1);
+            let carry_out = if bit == bits - 1 {
+                format!("z{bits:02}")
+            } else {
+                format!("co{bit:02}")
+            };
+
+            puzzle.add_gate(Operator::Xor, &[&x, &y], &swapped(hs.clone()));
+            puzzle.add_gate(Operator::And, &[&x, &y], &swapped(hc.clone()));
+            puzzle.add_gate(
+                Operator::Xor,
+                &[&hs, &carry_in],
+                &swapped(format!("z{bit:02}")),
+            );
+            puzzle.add_gate(Operator::And, &[&hs, &carry_in], &swapped(cd.clone()));
+            puzzle.add_gate(Operator::Or, &[&hc, &cd], &swapped(carry_out));
+        }
+        puzzle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BusOperation, Equivalence};
+    use aoc_common::Rng;
+
+    #[test]
+    fn builds_an_adder() {
+        let puzzle = Puzzle::ripple_carry_adder(8, &[]);
+        assert_eq!(puzzle.queue.len(), 2 + 7 * 5);
+        assert_eq!(
+            puzzle.computes(BusOperation::Add),
+            Ok(Equivalence::Equivalent)
+        );
+    }
+
+    #[test]
+    fn shuffled_adder_is_repaired() {
+        let swaps = [
+            ("z05", "co05"),
+            ("hs15", "hc15"),
+            ("z31", "cd31"),
+            ("z60", "co60"),
+        ];
+        let mut puzzle = Puzzle::ripple_carry_adder(63, &swaps);
+        Rng::new(24).shuffle(&mut puzzle.queue);
+
+        let parsed: Puzzle = puzzle.to_string().parse().unwrap();
+        assert_eq!(
+            parsed.solve_part2().unwrap(),
+            "cd31,co05,co60,hc15,hs15,z05,z31,z60"
+        );
+    }
+}
@@ -0,0 +1,754 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use aoc_common::{Error, Solution};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::alphanumeric1,
+    combinator::{map, value},
+    sequence::tuple,
+    IResult,
+};
+use num_bigint::BigUint;
+
+mod analysis;
+mod bdd;
+mod bus;
+mod compiled;
+mod cone;
+mod equivalence;
+mod export;
+mod generate;
+mod simulate;
+mod trace;
+mod verify;
+mod wires;
+
+pub use analysis::{AdderAnalysis, GateAnalysis, GateRole};
+pub use bus::bus_bit;
+pub use compiled::{CompiledCircuit, LANES};
+pub use cone::{BusBits, DepthSummary, OutputDepth, WireGroups};
+pub use equivalence::{BusOperation, Counterexample, Equivalence};
+pub use export::GraphFormat;
+pub use simulate::CircuitError;
+pub use trace::{Trace, TraceStep, WireValue};
+pub use verify::{Verification, VerifyError};
+pub use wires::{WireId, Wires};
+
+const GATE_EXPECTED: &str =
+    "a gate `<wire> AND|OR|XOR|NAND|NOR|XNOR <wire> -> <wire>`, `NOT <wire> -> <wire>` or `0|1 -> <wire>`";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    And,
+    Or,
+    Xor,
+    Nand,
+    Nor,
+    Xnor,
+    Not,
+    /// A source that always drives `0`.
+    Zero,
+    /// A source that always drives `1`.
+    One,
+}
+
+/// A gate driving the wire `result`. The number of operands matches [`Operator::arity`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sentence {
+    pub operator: Operator,
+    pub operands: Vec<WireId>,
+    pub result: WireId,
+}
+
+/// A circuit whose wires are interned in `wires`. The initial values and the gates refer to
+/// wires by id.
+///
+/// Two puzzles are equal if they have the same initial values and gates by wire name, even
+/// if their wires were interned in a different order.
+#[derive(Debug, Clone, Default)]
+pub struct Puzzle {
+    pub wires: Wires,
+    pub solution: HashMap<WireId, u8>,
+    pub queue: Vec<Sentence>,
+}
+
+/// A gate with the names of its operands and result.
+type NamedGate<'a> = (Operator, Vec<&'a str>, &'a str);
+
+impl Puzzle {
+    /// Sets the initial value of a wire.
+    pub fn set_initial(&mut self, wire: &str, value: u8) {
+        let id = self.wires.intern(wire);
+        self.solution.insert(id, value);
+    }
+
+    /// Appends a gate that combines the `operands` with `operator` and drives `result`.
+    pub fn add_gate(&mut self, operator: Operator, operands: &[&str], result: &str) {
+        let operands = operands.iter().map(|w| self.wires.intern(w)).collect();
+        let result = self.wires.intern(result);
+        self.queue.push(Sentence {
+            operator,
+            operands,
+            result,
+        });
+    }
+
+    /// Returns the name of a wire.
+    pub fn name(&self, id: WireId) -> &str {
+        self.wires.name(id)
+    }
+
+    pub fn solve_part1(&self) -> Result<BigUint, CircuitError> {
+        self.read_bus("z")
+    }
+
+    /// Returns the sorted names of the eight wires whose gates have to be swapped in pairs to
+    /// repair the adder.
+    ///
+    /// The adder is repaired bit by bit, which assumes that both gates of every swapped pair
+    /// belong to the same full adder. Swaps across bits can be undone with more than four
+    /// swaps, and such circuits are reported as unsolvable.
+    pub fn solve_part2(&self) -> Result<String, Error> {
+        let pairs = self.swapped_wire_pairs().ok_or_else(|| {
+            Error::Unsolvable("Circuit is not a repairable ripple-carry adder".to_string())
+        })?;
+        if pairs.len() != 4 {
+            return Err(Error::Unsolvable(format!(
+                "Repairing the adder bit by bit needs {} swapped pairs of wires instead of 4, \
+                 swaps are assumed to stay within one full adder",
+                pairs.len()
+            )));
+        }
+
+        let mut result: Vec<&str> = pairs
+            .into_iter()
+            .flat_map(|(a, b)| [self.name(a), self.name(b)])
+            .collect();
+        result.sort();
+        Ok(result.join(","))
+    }
+
+    /// Walks the circuit bit by bit as a ripple-carry adder and returns the pairs of gate
+    /// outputs that have to be swapped to make it add `x` and `y` into `z`.
+    ///
+    /// Every bit `i > 0` is expected to be built as
+    /// `x XOR y -> a`, `x AND y -> b`, `a XOR carry -> z`, `a AND carry -> d`, `b OR d -> carry'`.
+    fn swapped_wire_pairs(&self) -> Option<Vec<(WireId, WireId)>> {
+        let mut gates = self.queue.clone();
+        let mut swaps = Vec::new();
+        let bits = self.wires.bus("x").len();
+        // set by bit 0 before it is read
+        let mut carry: WireId = 0;
+
+        for bit in 0..bits {
+            let x = self.wires.id(&format!("x{bit:02}"))?;
+            let y = self.wires.id(&format!("y{bit:02}"))?;
+            let z = self.wires.id(&format!("z{bit:02}"))?;
+
+            loop {
+                if swaps.len() > gates.len() {
+                    return None;
+                }
+
+                let half_sum = Self::gate_output(&gates, Operator::Xor, x, y)?;
+                let half_carry = Self::gate_output(&gates, Operator::And, x, y)?;
+
+                if bit == 0 {
+                    if half_sum != z {
+                        Self::swap_outputs(&mut gates, &mut swaps, &mut carry, half_sum, z)?;
+                        continue;
+                    }
+                    carry = half_carry;
+                    break;
+                }
+
+                let Some(sum) = Self::gate_output(&gates, Operator::Xor, half_sum, carry) else {
+                    if let Some(other) = Self::gate_partner(&gates, Operator::Xor, carry) {
+                        Self::swap_outputs(&mut gates, &mut swaps, &mut carry, half_sum, other)?;
+                    } else {
+                        let other = Self::gate_partner(&gates, Operator::Xor, half_sum)?;
+                        let current_carry = carry;
+                        Self::swap_outputs(
+                            &mut gates,
+                            &mut swaps,
+                            &mut carry,
+                            current_carry,
+                            other,
+                        )?;
+                    }
+                    continue;
+                };
+
+                if sum != z {
+                    Self::swap_outputs(&mut gates, &mut swaps, &mut carry, sum, z)?;
+                    continue;
+                }
+
+                let carry_detail = Self::gate_output(&gates, Operator::And, half_sum, carry)?;
+                let Some(carry_out) =
+                    Self::gate_output(&gates, Operator::Or, half_carry, carry_detail)
+                else {
+                    if let Some(other) = Self::gate_partner(&gates, Operator::Or, half_carry) {
+                        Self::swap_outputs(
+                            &mut gates,
+                            &mut swaps,
+                            &mut carry,
+                            carry_detail,
+                            other,
+                        )?;
+                    } else {
+                        let other = Self::gate_partner(&gates, Operator::Or, carry_detail)?;
+                        Self::swap_outputs(&mut gates, &mut swaps, &mut carry, half_carry, other)?;
+                    }
+                    continue;
+                };
+
+                carry = carry_out;
+                break;
+            }
+        }
+
+        // the carry of the most significant bit is the highest output bit
+        if let Some(z) = self.wires.id(&format!("z{bits:02}")) {
+            if bits > 0 && carry != z && gates.iter().any(|g| g.result == z) {
+                let current_carry = carry;
+                Self::swap_outputs(&mut gates, &mut swaps, &mut carry, current_carry, z)?;
+            }
+        }
+
+        Some(swaps)
+    }
+
+    /// Returns the output wire of the gate combining `a` and `b` with `operator`.
+    fn gate_output(gates: &[Sentence], operator: Operator, a: WireId, b: WireId) -> Option<WireId> {
+        gates
+            .iter()
+            .find(|g| g.operator == operator && (g.operands == [a, b] || g.operands == [b, a]))
+            .map(|g| g.result)
+    }
+
+    /// Returns the other operand of a gate that combines `wire` with something using `operator`.
+    fn gate_partner(gates: &[Sentence], operator: Operator, wire: WireId) -> Option<WireId> {
+        gates
+            .iter()
+            .filter(|g| g.operator == operator)
+            .find_map(|g| match g.operands.as_slice() {
+                [a, b] if *a == wire => Some(*b),
+                [a, b] if *b == wire => Some(*a),
+                _ => None,
+            })
+    }
+
+    /// Swaps the outputs of the gates driving `a` and `b` and keeps track of the carry wire.
+    fn swap_outputs(
+        gates: &mut [Sentence],
+        swaps: &mut Vec<(WireId, WireId)>,
+        carry: &mut WireId,
+        a: WireId,
+        b: WireId,
+    ) -> Option<()> {
+        let driven = |wire: WireId| gates.iter().any(|g| g.result == wire);
+        if a == b || !driven(a) || !driven(b) {
+            return None;
+        }
+
+        for gate in gates.iter_mut() {
+            if gate.result == a {
+                gate.result = b;
+            } else if gate.result == b {
+                gate.result = a;
+            }
+        }
+
+        if *carry == a {
+            *carry = b;
+        } else if *carry == b {
+            *carry = a;
+        }
+
+        swaps.push((a, b));
+        Some(())
+    }
+
+    /// Returns the initial values and gates by wire name, ordered such that they don't
+    /// depend on the order in which the wires were interned.
+    fn named(&self) -> (Vec<(&str, u8)>, Vec<NamedGate<'_>>) {
+        let mut solution: Vec<(&str, u8)> = self
+            .solution
+            .iter()
+            .map(|(wire, value)| (self.name(*wire), *value))
+            .collect();
+        solution.sort();
+
+        let queue = self
+            .queue
+            .iter()
+            .map(|s| {
+                let operands = s.operands.iter().map(|w| self.name(*w)).collect();
+                (s.operator, operands, self.name(s.result))
+            })
+            .collect();
+        (solution, queue)
+    }
+
+    /// Parses every line of `section` with `parser`. The section has to be a slice of `input`,
+    /// which is used to report the location of errors.
+    fn parse_lines<'a, T>(
+        input: &str,
+        section: &'a str,
+        parser: fn(&'a str) -> IResult<&'a str, T>,
+        expected: &str,
+    ) -> Result<Vec<T>, Error> {
+        aoc_common::lines(section)
+            .map(|line| match parser(line.trim_end()) {
+                Ok(("", value)) => Ok(value),
+                Ok((rest, _)) => Err(Error::parse(input, rest, "end of line")),
+                Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+                    Err(Error::parse(input, e.input, expected))
+                }
+                Err(nom::Err::Incomplete(_)) => Err(Error::parse(input, line, expected)),
+            })
+            .collect()
+    }
+
+    fn parse_solution(input: &str) -> IResult<&str, (&str, u8)> {
+        let (input, (name, _, digit)) = tuple((
+            alphanumeric1,
+            tag(": "),
+            alt((value(0_u8, tag("0")), value(1_u8, tag("1")))),
+        ))(input)?;
+
+        Ok((input, (name, digit)))
+    }
+
+    /// Parses a gate in one of the forms `a OP b -> c`, `NOT a -> c` or `0|1 -> c`.
+    ///
+    /// Binary gates are tried last, so errors point behind the first operand.
+    fn parse_sentence(input: &str) -> IResult<&str, NamedGate<'_>> {
+        let constant = map(
+            tuple((
+                alt((
+                    value(Operator::Zero, tag("0")),
+                    value(Operator::One, tag("1")),
+                )),
+                tag(" -> "),
+                alphanumeric1,
+            )),
+            |(operator, _, result)| (operator, Vec::new(), result),
+        );
+        let unary = map(
+            tuple((tag("NOT "), alphanumeric1, tag(" -> "), alphanumeric1)),
+            |(_, operand, _, result)| (Operator::Not, vec![operand], result),
+        );
+        let binary = map(
+            tuple((
+                alphanumeric1,
+                alt((
+                    value(Operator::And, tag(" AND ")),
+                    value(Operator::Xor, tag(" XOR ")),
+                    value(Operator::Or, tag(" OR ")),
+                    value(Operator::Nand, tag(" NAND ")),
+                    value(Operator::Nor, tag(" NOR ")),
+                    value(Operator::Xnor, tag(" XNOR ")),
+                )),
+                alphanumeric1,
+                tag(" -> "),
+                alphanumeric1,
+            )),
+            |(operand1, operator, operand2, _, result)| {
+                (operator, vec![operand1, operand2], result)
+            },
+        );
+
+        alt((constant, unary, binary))(input)
+    }
+}
+
+impl PartialEq for Puzzle {
+    fn eq(&self, other: &Self) -> bool {
+        self.named() == other.named()
+    }
+}
+
+impl Eq for Puzzle {}
+
+impl FromStr for Puzzle {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut sections = aoc_common::sections(input);
+        // without initial values the gates are the only section
+        if sections.len() == 1 && sections[0].lines().next().is_some_and(|l| l.contains("->")) {
+            sections.insert(0, "");
+        }
+        let section = |index: usize| sections.get(index).copied().unwrap_or("");
+
+        let solution = Puzzle::parse_lines(
+            input,
+            section(0),
+            Puzzle::parse_solution,
+            "an initial wire value `<wire>: 0|1`",
+        )?;
+        let queue = Puzzle::parse_lines(input, section(1), Puzzle::parse_sentence, GATE_EXPECTED)?;
+        if let Some(extra) = sections.get(2) {
+            return Err(Error::parse(input, extra, "end of input"));
+        }
+
+        let mut puzzle = Puzzle::default();
+        for (wire, value) in solution {
+            puzzle.set_initial(wire, value);
+        }
+        for (operator, operands, result) in queue {
+            puzzle.add_gate(operator, &operands, result);
+        }
+        Ok(puzzle)
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Operator::And => "AND",
+            Operator::Or => "OR",
+            Operator::Xor => "XOR",
+            Operator::Nand => "NAND",
+            Operator::Nor => "NOR",
+            Operator::Xnor => "XNOR",
+            Operator::Not => "NOT",
+            Operator::Zero => "0",
+            Operator::One => "1",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Writes a gate in the input format, resolving the wire names.
+pub struct SentenceDisplay<'a> {
+    sentence: &'a Sentence,
+    wires: &'a Wires,
+}
+
+impl Sentence {
+    /// Returns a [`Display`] implementation that writes the gate with the names of `wires`.
+    pub fn display<'a>(&'a self, wires: &'a Wires) -> SentenceDisplay<'a> {
+        SentenceDisplay {
+            sentence: self,
+            wires,
+        }
+    }
+}
+
+impl Display for SentenceDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = self.sentence.operator;
+        let result = self.wires.name(self.sentence.result);
+        match self.sentence.operands.as_slice() {
+            [a, b] => {
+                let (a, b) = (self.wires.name(*a), self.wires.name(*b));
+                write!(f, "{a} {operator} {b} -> {result}")
+            }
+            [a] => write!(f, "{operator} {} -> {result}", self.wires.name(*a)),
+            _ => write!(f, "{operator} -> {result}"),
+        }
+    }
+}
+
+/// Writes the puzzle in the input format. The initial wire values are sorted by name,
+/// the gates keep their order. Without initial values only the gates are written.
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (solution, _) = self.named();
+
+        if !solution.is_empty() {
+            for (wire, value) in solution {
+                writeln!(f, "{wire}: {value}")?;
+            }
+            writeln!(f)?;
+        }
+        for sentence in &self.queue {
+            writeln!(f, "{}", sentence.display(&self.wires))?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part1(puzzle: &Self::Input) -> Result<impl Display, Error> {
+        Ok(puzzle.solve_part1()?)
+    }
+
+    fn part2(puzzle: &Self::Input) -> Result<impl Display, Error> {
+        puzzle.solve_part2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Builds a ripple-carry adder of the given width with the inputs `x` and `y` and swaps
+    /// the listed gate outputs.
+    pub(crate) fn adder(bits: usize, x: u64, y: u64, swaps: &[(&str, &str)]) -> Puzzle {
+        let mut puzzle = Puzzle::ripple_carry_adder(bits, swaps);
+        let bit_of =
+            |value: u64, bit: usize| (value.checked_shr(bit as u32).unwrap_or(0) & 1) as u8;
+        for bit in 0..bits {
+            puzzle.set_initial(&format!("x{bit:02}"), bit_of(x, bit));
+            puzzle.set_initial(&format!("y{bit:02}"), bit_of(y, bit));
+        }
+        puzzle
+    }
+
+    #[test]
+    fn part1_adds_inputs() {
+        let puzzle = adder(5, 13, 22, &[]);
+        assert_eq!(puzzle.solve_part1(), Ok(BigUint::from(35_u32)));
+    }
+
+    #[test]
+    fn part2_correct_adder_has_no_swaps() {
+        let puzzle = adder(5, 0, 0, &[]);
+        assert_eq!(puzzle.swapped_wire_pairs(), Some(Vec::new()));
+    }
+
+    #[test]
+    fn part2_single_swap() {
+        let puzzle = adder(4, 0, 0, &[("z02", "co02")]);
+        let pairs: Vec<(&str, &str)> = puzzle
+            .swapped_wire_pairs()
+            .unwrap()
+            .into_iter()
+            .map(|(a, b)| {
+                let (a, b) = (puzzle.name(a), puzzle.name(b));
+                (a.min(b), a.max(b))
+            })
+            .collect();
+        assert_eq!(pairs, vec![("co02", "z02")]);
+    }
+
+    #[test]
+    fn part2_four_swaps() {
+        let puzzle = adder(
+            6,
+            0,
+            0,
+            &[
+                ("z00", "co00"),
+                ("hs01", "hc01"),
+                ("z03", "cd03"),
+                ("z04", "co04"),
+            ],
+        );
+        assert_eq!(
+            puzzle.solve_part2().unwrap(),
+            "cd03,co00,co04,hc01,hs01,z00,z03,z04"
+        );
+
+        let mut repaired = puzzle.clone();
+        for (a, b) in puzzle.swapped_wire_pairs().unwrap() {
+            for gate in &mut repaired.queue {
+                if gate.result == a {
+                    gate.result = b;
+                } else if gate.result == b {
+                    gate.result = a;
+                }
+            }
+        }
+        assert_eq!(
+            repaired.computes(BusOperation::Add),
+            Ok(Equivalence::Equivalent)
+        );
+    }
+
+    #[test]
+    fn part2_needs_four_swaps() {
+        let puzzle = adder(45, 0, 0, &[("hs10", "hc10"), ("z11", "co11")]);
+        assert_eq!(
+            puzzle.solve_part2().unwrap_err().to_string(),
+            "Repairing the adder bit by bit needs 2 swapped pairs of wires instead of 4, \
+             swaps are assumed to stay within one full adder"
+        );
+        let no_inputs: Puzzle = "a: 1\n\nNOT a -> z00".parse().unwrap();
+        assert_eq!(
+            no_inputs.solve_part2().unwrap_err().to_string(),
+            "Repairing the adder bit by bit needs 0 swapped pairs of wires instead of 4, \
+             swaps are assumed to stay within one full adder"
+        );
+    }
+
+    #[test]
+    fn part2_swaps_across_bits_are_unsolvable() {
+        let swaps = [
+            ("hc10", "hc12"),
+            ("z20", "co20"),
+            ("hs30", "hc30"),
+            ("z40", "cd40"),
+        ];
+        let puzzle = adder(45, 0, 0, &swaps);
+        assert_eq!(
+            puzzle.solve_part2().unwrap_err().to_string(),
+            "Repairing the adder bit by bit needs 7 swapped pairs of wires instead of 4, \
+             swaps are assumed to stay within one full adder"
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let actual = "x00: 1\ny00: 2\n\nx00 AND y00 -> z00".parse::<Puzzle>();
+        assert_eq!(
+            actual.unwrap_err().to_string(),
+            "line 2, column 6: expected an initial wire value `<wire>: 0|1`, found `2`"
+        );
+
+        let actual = "x00: 1\ny00: 0\n\nx00 AND y00 -> z00\nx00 IMPLY y00 -> z01".parse::<Puzzle>();
+        assert_eq!(
+            actual.unwrap_err().to_string(),
+            format!("line 5, column 4: expected {GATE_EXPECTED}, found ` IMPLY y00 -> z01`")
+        );
+
+        let actual = "x00: 1\n\nx00 AND y00 -> z00 z01".parse::<Puzzle>();
+        assert_eq!(
+            actual.unwrap_err().to_string(),
+            "line 3, column 19: expected end of line, found ` z01`"
+        );
+
+        let actual = "x00: 1\n\nx00 AND x00 -> z00\n\nx00 OR x00 -> z01\n".parse::<Puzzle>();
+        assert_eq!(
+            actual.unwrap_err().to_string(),
+            "line 5, column 1: expected end of input, found `x00 OR x00 -> z01`"
+        );
+    }
+
+    #[test]
+    fn parses_extended_gates() {
+        let input = "x00: 1\n\n1 -> a\n0 -> b\nNOT x00 -> c\na NAND x00 -> d\nb NOR c -> e\nd XNOR e -> z00\n";
+        let puzzle: Puzzle = input.parse().unwrap();
+        let wire = |name| puzzle.wires.id(name).unwrap();
+        assert_eq!(
+            puzzle.queue[0],
+            Sentence {
+                operator: Operator::One,
+                operands: Vec::new(),
+                result: wire("a"),
+            }
+        );
+        assert_eq!(
+            puzzle.queue[2],
+            Sentence {
+                operator: Operator::Not,
+                operands: vec![wire("x00")],
+                result: wire("c"),
+            }
+        );
+        assert_eq!(puzzle.queue[3].operator, Operator::Nand);
+        assert_eq!(
+            puzzle.queue[3].display(&puzzle.wires).to_string(),
+            "a NAND x00 -> d"
+        );
+        assert_eq!(puzzle.to_string(), input);
+    }
+
+    #[test]
+    fn display_writes_input_format() {
+        let input =
+            "x00: 1\nx01: 0\ny00: 1\n\ny00 XOR x01 -> z01\nx00 AND y00 -> z00\nz00 OR z01 -> z02\n";
+        let puzzle: Puzzle = input.parse().unwrap();
+        assert_eq!(puzzle.to_string(), input);
+    }
+
+    #[test]
+    fn display_without_initial_values() {
+        let mut puzzle = Puzzle::default();
+        puzzle.add_gate(Operator::One, &[], "a");
+        puzzle.add_gate(Operator::Not, &["a"], "z00");
+        assert_eq!(puzzle.to_string(), "1 -> a\nNOT a -> z00\n");
+
+        assert_eq!(
+            "1 -> a\nNOT a -> z00\n".parse::<Puzzle>(),
+            Ok(puzzle.clone())
+        );
+        assert_eq!("\n1 -> a\nNOT a -> z00\n".parse::<Puzzle>(), Ok(puzzle));
+    }
+
+    #[test]
+    fn equality_ignores_interning_order() {
+        let mut a = Puzzle::default();
+        a.set_initial("x00", 1);
+        a.set_initial("y00", 0);
+        a.add_gate(Operator::And, &["x00", "y00"], "z00");
+
+        let mut b = Puzzle::default();
+        b.set_initial("y00", 0);
+        b.add_gate(Operator::And, &["x00", "y00"], "z00");
+        b.set_initial("x00", 1);
+
+        assert_ne!(a.wires.id("y00"), b.wires.id("y00"));
+        assert_eq!(a, b);
+        b.set_initial("x00", 0);
+        assert_ne!(a, b);
+    }
+
+    fn wire() -> impl Strategy<Value = String> {
+        "[a-z][a-z0-9]{0,3}"
+    }
+
+    fn gate() -> impl Strategy<Value = (Operator, Vec<String>, String)> {
+        let operator = prop_oneof![
+            Just(Operator::And),
+            Just(Operator::Or),
+            Just(Operator::Xor),
+            Just(Operator::Nand),
+            Just(Operator::Nor),
+            Just(Operator::Xnor),
+            Just(Operator::Not),
+            Just(Operator::Zero),
+            Just(Operator::One),
+        ];
+        (operator, wire(), wire(), wire()).prop_map(|(operator, operand1, operand2, result)| {
+            let operands = [operand1, operand2]
+                .into_iter()
+                .take(operator.arity())
+                .collect();
+            (operator, operands, result)
+        })
+    }
+
+    fn puzzle() -> impl Strategy<Value = Puzzle> {
+        (
+            prop::collection::hash_map(wire(), 0_u8..=1, 0..10),
+            prop::collection::vec(gate(), 0..20),
+        )
+            .prop_map(|(solution, queue)| {
+                let mut puzzle = Puzzle::default();
+                for (wire, value) in &solution {
+                    puzzle.set_initial(wire, *value);
+                }
+                for (operator, operands, result) in &queue {
+                    let operands: Vec<&str> = operands.iter().map(String::as_str).collect();
+                    puzzle.add_gate(*operator, &operands, result);
+                }
+                puzzle
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trips(puzzle in puzzle()) {
+            let parsed: Puzzle = puzzle.to_string().parse().unwrap();
+            prop_assert_eq!(parsed, puzzle);
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::{Operator, Puzzle, WireId, LANES};
+
+/// Reasons why a circuit can't be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// Wires used as operands that are neither initialized nor driven by a gate.
+    Undriven(Vec<String>),
+    /// A wire that is driven more than once, by gates or by an initial value.
+    MultipleDrivers(String),
+    /// Wires that depend on themselves, in the order of the cycle.
+    Cycle(Vec<String>),
+    /// A value that has more bits than the input bus it should be assigned to.
+    BusOverflow { bus: String, width: usize },
+    /// Initial wires that exist in only one of two circuits that are compared.
+    InputMismatch(Vec<String>),
+    /// A circuit whose symbolic representation grew beyond the node limit.
+    TooComplex { nodes: usize },
+    /// A wire name that doesn't occur in the circuit.
+    UnknownWire(String),
+    /// More values than the compiled circuit simulates at once.
+    TooManyLanes(usize),
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitError::Undriven(wires) => {
+                write!(f, "Wires without a driver: {}", wires.join(", "))
+            }
+            CircuitError::MultipleDrivers(wire) => {
+                write!(f, "Wire {wire} is driven more than once")
+            }
+            CircuitError::Cycle(wires) => {
+                write!(
+                    f,
+                    "Wires form a cycle: {} -> {}",
+                    wires.join(" -> "),
+                    wires[0]
+                )
+            }
+            CircuitError::BusOverflow { bus, width } => {
+                write!(f, "Value doesn't fit into the {width} bits of bus {bus}")
+            }
+            CircuitError::InputMismatch(wires) => {
+                write!(
+                    f,
+                    "Inputs only present in one circuit: {}",
+                    wires.join(", ")
+                )
+            }
+            CircuitError::TooComplex { nodes } => {
+                write!(f, "Circuit needs more than {nodes} BDD nodes to compare")
+            }
+            CircuitError::UnknownWire(wire) => write!(f, "Unknown wire {wire}"),
+            CircuitError::TooManyLanes(lanes) => {
+                write!(
+                    f,
+                    "Got {lanes} values, but only {LANES} lanes are simulated at once"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+impl From<CircuitError> for aoc_common::Error {
+    fn from(value: CircuitError) -> Self {
+        aoc_common::Error::Unsolvable(value.to_string())
+    }
+}
+
+impl Operator {
+    /// Returns the number of operands a gate with this operator takes.
+    pub fn arity(&self) -> usize {
+        match self {
+            Operator::Zero | Operator::One => 0,
+            Operator::Not => 1,
+            _ => 2,
+        }
+    }
+
+    /// Applies the operator to the operand values, which have to match its [`arity`](Self::arity).
+    pub fn apply(&self, operands: &[u8]) -> u8 {
+        match (self, operands) {
+            (Operator::And, [a, b]) => a & b,
+            (Operator::Or, [a, b]) => a | b,
+            (Operator::Xor, [a, b]) => a ^ b,
+            (Operator::Nand, [a, b]) => 1 ^ (a & b),
+            (Operator::Nor, [a, b]) => 1 ^ (a | b),
+            (Operator::Xnor, [a, b]) => 1 ^ a ^ b,
+            (Operator::Not, [a]) => 1 ^ a,
+            (Operator::Zero, []) => 0,
+            (Operator::One, []) => 1,
+            _ => panic!("{self} expects {} operands", self.arity()),
+        }
+    }
+}
+
+impl Puzzle {
+    /// Returns the indices of all gates in `queue`, ordered such that every gate comes after
+    /// the gates driving its operands.
+    pub fn evaluation_order(&self) -> Result<Vec<usize>, CircuitError> {
+        let mut drivers: Vec<Option<usize>> = vec![None; self.wires.len()];
+        for (index, sentence) in self.queue.iter().enumerate() {
+            let result = sentence.result;
+            if self.solution.contains_key(&result) || drivers[result].replace(index).is_some() {
+                return Err(CircuitError::MultipleDrivers(self.name(result).to_string()));
+            }
+        }
+
+        let mut pending = vec![0_usize; self.queue.len()];
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); self.queue.len()];
+        let mut undriven: Vec<String> = Vec::new();
+
+        for (index, sentence) in self.queue.iter().enumerate() {
+            for operand in &sentence.operands {
+                if self.solution.contains_key(operand) {
+                    continue;
+                }
+                match drivers[*operand] {
+                    Some(driver) => {
+                        pending[index] += 1;
+                        dependents[driver].push(index);
+                    }
+                    None => undriven.push(self.name(*operand).to_string()),
+                }
+            }
+        }
+
+        if !undriven.is_empty() {
+            undriven.sort();
+            undriven.dedup();
+            return Err(CircuitError::Undriven(undriven));
+        }
+
+        let mut ready: VecDeque<usize> = (0..self.queue.len())
+            .filter(|index| pending[*index] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.queue.len());
+
+        while let Some(index) = ready.pop_front() {
+            order.push(index);
+            for dependent in &dependents[index] {
+                pending[*dependent] -= 1;
+                if pending[*dependent] == 0 {
+                    ready.push_back(*dependent);
+                }
+            }
+        }
+
+        if order.len() < self.queue.len() {
+            return Err(CircuitError::Cycle(self.find_cycle(&pending, &drivers)));
+        }
+
+        Ok(order)
+    }
+
+    /// Evaluates all gates and returns the values of all wires, indexed by [`WireId`].
+    pub fn evaluate(&self) -> Result<Vec<u8>, CircuitError> {
+        let order = self.evaluation_order()?;
+        let mut values = vec![0; self.wires.len()];
+        for (wire, value) in &self.solution {
+            values[*wire] = *value;
+        }
+
+        let mut operands = Vec::with_capacity(2);
+        for index in order {
+            let sentence = &self.queue[index];
+            operands.clear();
+            operands.extend(sentence.operands.iter().map(|w| values[*w]));
+            values[sentence.result] = sentence.operator.apply(&operands);
+        }
+
+        Ok(values)
+    }
+
+    /// Walks backwards from a gate that couldn't be evaluated until a wire repeats.
+    ///
+    /// Every such gate has an operand driven by another one that couldn't be evaluated,
+    /// so the walk always ends in a cycle.
+    fn find_cycle(&self, pending: &[usize], drivers: &[Option<usize>]) -> Vec<String> {
+        let blocked = |wire: WireId| drivers[wire].filter(|d| pending[*d] > 0);
+
+        let mut index = pending.iter().position(|p| *p > 0).unwrap_or(0);
+        let mut path: Vec<usize> = Vec::new();
+
+        while !path.contains(&index) {
+            path.push(index);
+            let sentence = &self.queue[index];
+            match sentence
+                .operands
+                .iter()
+                .find_map(|operand| blocked(*operand))
+            {
+                Some(driver) => index = driver,
+                None => break,
+            }
+        }
+
+        let start = path.iter().position(|i| *i == index).unwrap_or(0);
+        let mut cycle: Vec<String> = path[start..]
+            .iter()
+            .rev()
+            .map(|i| self.name(self.queue[*i].result).to_string())
+            .collect();
+
+        // start the reported cycle with its smallest wire, so the error is deterministic
+        let smallest = (0..cycle.len()).min_by_key(|i| &cycle[*i]).unwrap_or(0);
+        cycle.rotate_left(smallest);
+        cycle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_in_dependency_order() {
+        let puzzle: Puzzle = "x00: 1\ny00: 0\n\nb OR y00 -> z00\nx00 XOR y00 -> a\na AND x00 -> b"
+            .parse()
+            .unwrap();
+
+        assert_eq!(puzzle.evaluation_order(), Ok(vec![1, 2, 0]));
+        let values = puzzle.evaluate().unwrap();
+        let value = |wire| values[puzzle.wires.id(wire).unwrap()];
+        assert_eq!(value("a"), 1);
+        assert_eq!(value("b"), 1);
+        assert_eq!(value("z00"), 1);
+    }
+
+    #[test]
+    fn evaluates_extended_gates() {
+        let puzzle: Puzzle = "x00: 1\n\n1 -> a\n0 -> b\nNOT x00 -> c\na NAND x00 -> d\n\
+            b NOR c -> e\nd XNOR e -> f\nNOT f -> z00"
+            .parse()
+            .unwrap();
+
+        let values = puzzle.evaluate().unwrap();
+        let value = |wire| values[puzzle.wires.id(wire).unwrap()];
+        let expected = [("a", 1), ("b", 0), ("c", 0), ("d", 0), ("e", 1), ("f", 0)];
+        for (wire, expected) in expected {
+            assert_eq!(value(wire), expected, "wire {wire}");
+        }
+        assert_eq!(value("z00"), 1);
+    }
+
+    #[test]
+    fn operators_follow_truth_tables() {
+        let table = |operator: Operator| -> Vec<u8> {
+            [[0, 0], [0, 1], [1, 0], [1, 1]]
+                .iter()
+                .map(|inputs| operator.apply(inputs))
+                .collect()
+        };
+        assert_eq!(table(Operator::And), vec![0, 0, 0, 1]);
+        assert_eq!(table(Operator::Or), vec![0, 1, 1, 1]);
+        assert_eq!(table(Operator::Xor), vec![0, 1, 1, 0]);
+        assert_eq!(table(Operator::Nand), vec![1, 1, 1, 0]);
+        assert_eq!(table(Operator::Nor), vec![1, 0, 0, 0]);
+        assert_eq!(table(Operator::Xnor), vec![1, 0, 0, 1]);
+        assert_eq!(Operator::Not.apply(&[0]), 1);
+        assert_eq!(Operator::Not.apply(&[1]), 0);
+        assert_eq!(Operator::Zero.apply(&[]), 0);
+        assert_eq!(Operator::One.apply(&[]), 1);
+    }
+
+    #[test]
+    fn detects_undriven_wires() {
+        let puzzle: Puzzle = "x00: 1\n\nx00 AND y00 -> a\nfoo OR a -> z00\ny00 XOR x00 -> z01"
+            .parse()
+            .unwrap();
+
+        let error = puzzle.evaluate().unwrap_err();
+        assert_eq!(
+            error,
+            CircuitError::Undriven(vec!["foo".to_string(), "y00".to_string()])
+        );
+        assert_eq!(error.to_string(), "Wires without a driver: foo, y00");
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let puzzle: Puzzle =
+            "x00: 1\n\nx00 AND c -> a\na OR x00 -> b\nb XOR x00 -> c\nc OR x00 -> z00"
+                .parse()
+                .unwrap();
+
+        let error = puzzle.evaluate().unwrap_err();
+        assert_eq!(
+            error,
+            CircuitError::Cycle(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+        assert_eq!(error.to_string(), "Wires form a cycle: a -> b -> c -> a");
+    }
+
+    #[test]
+    fn detects_multiple_drivers() {
+        let puzzle: Puzzle = "x00: 1\n\nx00 AND x00 -> a\nx00 OR x00 -> a"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            puzzle.evaluate(),
+            Err(CircuitError::MultipleDrivers("a".to_string()))
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{CircuitError, Puzzle, WireId};
+
+/// The value of a wire at the time it was read or written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WireValue {
+    pub wire: String,
+    pub value: u8,
+}
+
+/// The evaluation of one gate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceStep {
+    /// The position of the step in the trace, starting at 1.
+    pub step: usize,
+    /// The pass in which the gate could be evaluated: 1 if it only reads initial wires,
+    /// otherwise one more than the latest pass of the gates driving its operands.
+    pub pass: usize,
+    /// The gate in the input format.
+    pub gate: String,
+    pub inputs: Vec<WireValue>,
+    pub output: WireValue,
+}
+
+/// The steps of an evaluation, in the order the wires resolve in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Trace {
+    pub steps: Vec<TraceStep>,
+}
+
+impl fmt::Display for WireValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.wire, self.value)
+    }
+}
+
+/// Writes the trace as a table with one row per step.
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<[String; 5]> = self
+            .steps
+            .iter()
+            .map(|step| {
+                let inputs: Vec<String> = step.inputs.iter().map(|i| i.to_string()).collect();
+                [
+                    step.step.to_string(),
+                    step.pass.to_string(),
+                    step.gate.clone(),
+                    inputs.join(" "),
+                    step.output.to_string(),
+                ]
+            })
+            .collect();
+
+        let header = ["step", "pass", "gate", "inputs", "output"].map(String::from);
+        let mut widths = header.clone().map(|h| h.len());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        for row in [header].iter().chain(&rows) {
+            let [step, pass, gate, inputs, output] = row;
+            let [w0, w1, w2, w3, _] = widths;
+            writeln!(
+                f,
+                "{step:>w0$}  {pass:>w1$}  {gate:w2$}  {inputs:w3$}  {output}"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Puzzle {
+    /// Evaluates the circuit and records every gate evaluation. If `output` is set, only
+    /// the gates in its cone of influence, the gates it transitively depends on, are traced.
+    pub fn trace(&self, output: Option<&str>) -> Result<Trace, CircuitError> {
+        let order = self.evaluation_order()?;
+
+        let mut included = vec![true; self.queue.len()];
+        if let Some(output) = output {
+            let reached = self.fan_in_wires(self.wire_id(output)?);
+            included = self.queue.iter().map(|s| reached[s.result]).collect();
+        }
+
+        let mut values = vec![0; self.wires.len()];
+        for (wire, value) in &self.solution {
+            values[*wire] = *value;
+        }
+        let mut passes = vec![0; self.wires.len()];
+        let mut steps = Vec::new();
+        let mut operands = Vec::with_capacity(2);
+
+        for index in order.into_iter().filter(|index| included[*index]) {
+            let sentence = &self.queue[index];
+            operands.clear();
+            operands.extend(sentence.operands.iter().map(|w| values[*w]));
+            let value = sentence.operator.apply(&operands);
+            let pass = 1 + sentence
+                .operands
+                .iter()
+                .map(|w| passes[*w])
+                .max()
+                .unwrap_or(0);
+            values[sentence.result] = value;
+            passes[sentence.result] = pass;
+
+            steps.push(TraceStep {
+                step: 0,
+                pass,
+                gate: sentence.display(&self.wires).to_string(),
+                inputs: sentence
+                    .operands
+                    .iter()
+                    .map(|w| self.wire_value(*w, values[*w]))
+                    .collect(),
+                output: self.wire_value(sentence.result, value),
+            });
+        }
+
+        steps.sort_by_key(|step| step.pass);
+        for (index, step) in steps.iter_mut().enumerate() {
+            step.step = index + 1;
+        }
+        Ok(Trace { steps })
+    }
+
+    fn wire_value(&self, wire: WireId, value: u8) -> WireValue {
+        WireValue {
+            wire: self.name(wire).to_string(),
+            value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CIRCUIT: &str = "x00: 1\nx01: 0\ny00: 1\ny01: 1\n\n\
+        s01 XOR c00 -> z01\nx00 XOR y00 -> z00\nx00 AND y00 -> c00\nx01 XOR y01 -> s01\n\
+        x01 AND y01 -> c01";
+
+    #[test]
+    fn records_every_gate() {
+        let puzzle: Puzzle = CIRCUIT.parse().unwrap();
+        let trace = puzzle.trace(None).unwrap();
+
+        let gates: Vec<(usize, usize, &str)> = trace
+            .steps
+            .iter()
+            .map(|s| (s.step, s.pass, s.gate.as_str()))
+            .collect();
+        assert_eq!(
+            gates,
+            vec![
+                (1, 1, "x00 XOR y00 -> z00"),
+                (2, 1, "x00 AND y00 -> c00"),
+                (3, 1, "x01 XOR y01 -> s01"),
+                (4, 1, "x01 AND y01 -> c01"),
+                (5, 2, "s01 XOR c00 -> z01"),
+            ]
+        );
+
+        let last = &trace.steps[4];
+        let value = |wire: &str, value| WireValue {
+            wire: wire.to_string(),
+            value,
+        };
+        assert_eq!(last.inputs, vec![value("s01", 1), value("c00", 1)]);
+        assert_eq!(last.output, value("z01", 0));
+    }
+
+    #[test]
+    fn limits_trace_to_cone_of_influence() {
+        let puzzle: Puzzle = CIRCUIT.parse().unwrap();
+        let trace = puzzle.trace(Some("z01")).unwrap();
+
+        let outputs: Vec<&str> = trace.steps.iter().map(|s| s.output.wire.as_str()).collect();
+        assert_eq!(outputs, vec!["c00", "s01", "z01"]);
+        assert_eq!(
+            puzzle.trace(Some("z07")),
+            Err(CircuitError::UnknownWire("z07".to_string()))
+        );
+    }
+
+    #[test]
+    fn writes_table() {
+        let puzzle: Puzzle = CIRCUIT.parse().unwrap();
+        let table = puzzle.trace(Some("z01")).unwrap().to_string();
+
+        assert_eq!(
+            table,
+            concat!(
+                "step  pass  gate                inputs       output\n",
+                "   1     1  x00 AND y00 -> c00  x00=1 y00=1  c00=1\n",
+                "   2     1  x01 XOR y01 -> s01  x01=0 y01=1  s01=1\n",
+                "   3     2  s01 XOR c00 -> z01  s01=1 c00=1  z01=0\n",
+            )
+        );
+    }
+
+    #[test]
+    fn serializes_steps_to_json() {
+        let puzzle: Puzzle = CIRCUIT.parse().unwrap();
+        let trace = puzzle.trace(Some("c00")).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&trace.steps[0]).unwrap(),
+            r#"{"step":1,"pass":1,"gate":"x00 AND y00 -> c00","inputs":[{"wire":"x00","value":1},{"wire":"y00","value":1}],"output":{"wire":"c00","value":1}}"#
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use aoc_common::Rng;
+use num_bigint::BigUint;
+
+use crate::compiled::LANES;
+use crate::{bus_bit, BusOperation, CircuitError, Counterexample, Puzzle};
+
+/// Circuits whose `x` and `y` buses have at most this many bits together are verified with
+/// every pair of values. Wider circuits are verified with random values.
+const EXHAUSTIVE_BITS: usize = 16;
+
+/// The number of failing vectors that are kept.
+const MAX_FAILURES: usize = 8;
+
+/// The result of simulating a circuit with many values of its `x` and `y` buses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    /// The number of simulated pairs of bus values.
+    pub vectors: u64,
+    /// Whether every pair of bus values was simulated.
+    pub exhaustive: bool,
+    /// The lowest output bit that was wrong for any vector.
+    pub failing_bit: Option<usize>,
+    /// The first few vectors whose lowest wrong output bit is `failing_bit`.
+    pub failures: Vec<Counterexample>,
+}
+
+/// Reasons why a circuit can't be verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    /// The circuit can't be evaluated.
+    Circuit(CircuitError),
+    /// The circuit is too wide to be verified exhaustively and no random vectors were asked for.
+    NoSamples,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::Circuit(error) => write!(f, "{error}"),
+            VerifyError::NoSamples => write!(
+                f,
+                "Need at least one sample to verify a circuit with more than {EXHAUSTIVE_BITS} input bits"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+impl From<CircuitError> for VerifyError {
+    fn from(value: CircuitError) -> Self {
+        VerifyError::Circuit(value)
+    }
+}
+
+/// Returns a random value with `width` bits.
+fn random_value(rng: &mut Rng, width: usize) -> BigUint {
+    let mut value = BigUint::default();
+    for chunk in (0..width).step_by(64) {
+        let bits = rng.next_u64();
+        for bit in chunk..width.min(chunk + 64) {
+            value.set_bit(bit as u64, bits >> (bit - chunk) & 1 == 1);
+        }
+    }
+    value
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = if self.exhaustive {
+            "every input"
+        } else {
+            "random inputs"
+        };
+        write!(f, "Simulated {} vectors with {mode}", self.vectors)?;
+        let Some(bit) = self.failing_bit else {
+            return writeln!(f, ", all outputs are correct");
+        };
+        writeln!(f, ", the lowest failing output is z{bit:02}:")?;
+        for failure in &self.failures {
+            writeln!(f, "{failure}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Puzzle {
+    /// Simulates the circuit with values of its `x` and `y` buses and compares the `z` bus
+    /// against `operation`. With up to 16 input bits every pair of values is simulated,
+    /// otherwise `samples` random pairs generated from `seed`.
+    ///
+    /// Unlike [`Puzzle::computes`] this doesn't prove that the circuit is correct, but it
+    /// finds most wrong wirings quickly on circuits of any width. Circuits that are verified
+    /// with random values need at least one sample.
+    pub fn verify(
+        &self,
+        operation: BusOperation,
+        samples: u64,
+        seed: u64,
+    ) -> Result<Verification, VerifyError> {
+        let widths = [self.input_width("x"), self.input_width("y")];
+        let exhaustive = widths[0] + widths[1] <= EXHAUSTIVE_BITS;
+        if !exhaustive && samples == 0 {
+            return Err(VerifyError::NoSamples);
+        }
+        let circuit = self.compile()?;
+        let vectors = if exhaustive {
+            1 << (widths[0] + widths[1])
+        } else {
+            samples
+        };
+
+        let mut rng = Rng::new(seed);
+        let mut verification = Verification {
+            vectors,
+            exhaustive,
+            failing_bit: None,
+            failures: Vec::new(),
+        };
+        let mut values = circuit.values();
+
+        for base in (0..vectors).step_by(LANES) {
+            let lanes = (vectors - base).min(LANES as u64);
+            let (x, y): (Vec<BigUint>, Vec<BigUint>) = (base..base + lanes)
+                .map(|vector| {
+                    if exhaustive {
+                        let mask = (1 << widths[0]) - 1;
+                        (
+                            BigUint::from(vector & mask),
+                            BigUint::from(vector >> widths[0]),
+                        )
+                    } else {
+                        (
+                            random_value(&mut rng, widths[0]),
+                            random_value(&mut rng, widths[1]),
+                        )
+                    }
+                })
+                .unzip();
+            circuit.set_bus(&mut values, "x", &x)?;
+            circuit.set_bus(&mut values, "y", &y)?;
+            circuit.evaluate(&mut values);
+
+            let z = circuit.read_bus(&values, "z");
+            for ((x, y), actual) in x.into_iter().zip(y).zip(z) {
+                let expected = operation.apply(&x, &y);
+                let Some(bit) = (&actual ^ &expected).trailing_zeros() else {
+                    continue;
+                };
+                let bit = bit as usize;
+                match verification.failing_bit {
+                    Some(lowest) if lowest < bit => continue,
+                    Some(lowest) if lowest == bit => {
+                        if verification.failures.len() == MAX_FAILURES {
+                            continue;
+                        }
+                    }
+                    _ => {
+                        verification.failing_bit = Some(bit);
+                        verification.failures.clear();
+                    }
+                }
+                verification.failures.push(Counterexample {
+                    inputs: self.bus_inputs(&x, &y),
+                    actual,
+                    expected,
+                });
+            }
+        }
+        Ok(verification)
+    }
+
+    /// Returns the values of all initial wires with the `x` and `y` buses set to `x` and `y`.
+    fn bus_inputs(&self, x: &BigUint, y: &BigUint) -> HashMap<String, u8> {
+        self.solution
+            .iter()
+            .map(|(wire, value)| {
+                let name = self.name(*wire);
+                let value = match (bus_bit(name, "x"), bus_bit(name, "y")) {
+                    (Some(bit), _) => u8::from(x.bit(bit as u64)),
+                    (_, Some(bit)) => u8::from(y.bit(bit as u64)),
+                    _ => *value,
+                };
+                (name.to_string(), value)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::bus_value_by_name;
+    use crate::tests::adder;
+
+    #[test]
+    fn small_adder_is_verified_exhaustively() {
+        let verification = adder(5, 0, 0, &[]).verify(BusOperation::Add, 0, 0).unwrap();
+
+        assert!(verification.exhaustive);
+        assert_eq!(verification.vectors, 1 << 10);
+        assert_eq!(verification.failing_bit, None);
+        assert_eq!(
+            verification.to_string(),
+            "Simulated 1024 vectors with every input, all outputs are correct\n"
+        );
+    }
+
+    #[test]
+    fn reports_lowest_failing_bit() {
+        let puzzle = adder(6, 0, 0, &[("z04", "co04"), ("z02", "cd02")]);
+        let verification = puzzle.verify(BusOperation::Add, 0, 0).unwrap();
+
+        assert_eq!(verification.failing_bit, Some(2));
+        assert_eq!(verification.failures.len(), MAX_FAILURES);
+        for failure in &verification.failures {
+            let x = bus_value_by_name(&failure.inputs, "x");
+            let y = bus_value_by_name(&failure.inputs, "y");
+            assert_eq!(failure.expected, &x + &y);
+            assert_eq!(
+                puzzle.compute(&[("x", &x), ("y", &y)], "z"),
+                Ok(failure.actual.clone())
+            );
+            assert_eq!(failure.bit(), 2);
+        }
+    }
+
+    #[test]
+    fn wide_adder_is_verified_with_random_values() {
+        let verification = adder(45, 0, 0, &[])
+            .verify(BusOperation::Add, 1000, 7)
+            .unwrap();
+        assert!(!verification.exhaustive);
+        assert_eq!(verification.vectors, 1000);
+        assert_eq!(verification.failing_bit, None);
+
+        let swapped = adder(45, 0, 0, &[("z20", "cd20")]);
+        let verification = swapped.verify(BusOperation::Add, 1000, 7).unwrap();
+        assert_eq!(verification.failing_bit, Some(20));
+        assert_eq!(
+            verification,
+            swapped.verify(BusOperation::Add, 1000, 7).unwrap()
+        );
+    }
+
+    #[test]
+    fn verifies_other_operations() {
+        let puzzle: Puzzle = "x00: 0\nx01: 0\ny00: 0\ny01: 0\n\nx00 AND y00 -> z00\n\
+            x01 OR y01 -> z01"
+            .parse()
+            .unwrap();
+
+        let and = puzzle.verify(BusOperation::And, 0, 0).unwrap();
+        assert_eq!(and.failing_bit, Some(1));
+        assert_eq!(and.failures.len(), 8);
+        let xor = puzzle.verify(BusOperation::Xor, 0, 0).unwrap();
+        assert_eq!(xor.failing_bit, Some(0));
+    }
+
+    #[test]
+    fn rejects_zero_samples() {
+        let puzzle = adder(45, 0, 0, &[]);
+        assert_eq!(
+            puzzle.verify(BusOperation::Add, 0, 0),
+            Err(VerifyError::NoSamples)
+        );
+    }
+}
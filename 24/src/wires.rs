@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use crate::bus_bit;
+
+/// The dense id of a wire, an index into [`Wires`] and into the value vectors of a circuit.
+pub type WireId = usize;
+
+/// Interns wire names, so the netlist can refer to wires by [`WireId`] instead of cloning
+/// names around. Ids are handed out in the order the names are first seen.
+#[derive(Debug, Clone, Default)]
+pub struct Wires {
+    names: Vec<String>,
+    ids: HashMap<String, WireId>,
+}
+
+impl Wires {
+    /// Returns the id of a wire, adding it if it wasn't seen before.
+    pub fn intern(&mut self, name: &str) -> WireId {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    /// Returns the id of a known wire.
+    pub fn id(&self, name: &str) -> Option<WireId> {
+        self.ids.get(name).copied()
+    }
+
+    /// Returns the name of a wire.
+    pub fn name(&self, id: WireId) -> &str {
+        &self.names[id]
+    }
+
+    /// Returns the number of wires.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Returns all wires with their names, ordered by id.
+    pub fn iter(&self) -> impl Iterator<Item = (WireId, &str)> {
+        self.names.iter().map(String::as_str).enumerate()
+    }
+
+    /// Returns the bit indices and ids of all wires of a bus, ordered by bit index.
+    pub fn bus(&self, prefix: &str) -> Vec<(usize, WireId)> {
+        let mut wires: Vec<(usize, WireId)> = self
+            .iter()
+            .filter_map(|(id, wire)| Some((bus_bit(wire, prefix)?, id)))
+            .collect();
+        wires.sort();
+        wires
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interns_names() {
+        let mut wires = Wires::default();
+        let z01 = wires.intern("z01");
+        let x00 = wires.intern("x00");
+        let z00 = wires.intern("z00");
+
+        assert_eq!(wires.intern("x00"), x00);
+        assert_eq!((z01, x00, z00), (0, 1, 2));
+        assert_eq!(wires.len(), 3);
+        assert_eq!(wires.name(z00), "z00");
+        assert_eq!(wires.id("z01"), Some(z01));
+        assert_eq!(wires.id("y00"), None);
+        assert_eq!(wires.bus("z"), vec![(0, z00), (1, z01)]);
+    }
+}
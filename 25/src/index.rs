@@ -0,0 +1,163 @@
+use rayon::prelude::*;
+
+use crate::{Key, Lock};
+
+/// Finds the locks a key fits into without comparing the key with every lock.
+///
+/// For every column and height there is a bitset of the locks whose pin in that column is
+/// at most that high. The locks a key fits into are the intersection of one bitset per
+/// column: the one for the height that is left next to the key's pin.
+#[derive(Debug, Clone)]
+pub struct FitIndex {
+    width: usize,
+    rows: usize,
+    /// The number of `u64` words of one bitset.
+    words: usize,
+    /// The bitsets, ordered by column and then by height from `0` to the space of a lock.
+    bitsets: Vec<u64>,
+}
+
+impl FitIndex {
+    /// Indexes locks of the same size. Locks with another size than the first one are
+    /// never found.
+    pub fn new(locks: &[Lock]) -> Self {
+        let (width, rows) = locks.first().map_or((0, 0), |l| (l.0.width(), l.0.rows));
+        let words = locks.len().div_ceil(64);
+        let heights = rows.saturating_sub(1);
+        let mut bitsets = vec![0; width * heights * words];
+
+        for (index, Lock(lock)) in locks.iter().enumerate() {
+            if lock.width() != width || lock.rows != rows {
+                continue;
+            }
+            for (column, height) in lock.heights.iter().enumerate() {
+                // the lock is also in the bitsets of all higher heights
+                for bitset in *height..heights {
+                    bitsets[(column * heights + bitset) * words + index / 64] |= 1 << (index % 64);
+                }
+            }
+        }
+
+        FitIndex {
+            width,
+            rows,
+            words,
+            bitsets,
+        }
+    }
+
+    /// Returns the indices of the locks the key fits into, in ascending order.
+    pub fn fitting(&self, key: &Key) -> impl Iterator<Item = usize> + '_ {
+        let matches = self.matches(key);
+        matches
+            .into_iter()
+            .enumerate()
+            .flat_map(|(word, mut bits)| {
+                std::iter::from_fn(move || {
+                    let bit = (bits != 0).then(|| bits.trailing_zeros() as usize)?;
+                    bits &= bits - 1;
+                    Some(word * 64 + bit)
+                })
+            })
+    }
+
+    /// Returns the number of locks the key fits into.
+    pub fn count(&self, key: &Key) -> usize {
+        self.matches(key)
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns the bitset of the locks the key fits into.
+    fn matches(&self, Key(key): &Key) -> Vec<u64> {
+        let heights = self.rows.saturating_sub(1);
+        let space = self.rows.saturating_sub(2);
+        if key.width() != self.width || key.rows != self.rows || heights == 0 {
+            return vec![0; self.words];
+        }
+
+        let mut matches = vec![u64::MAX; self.words];
+        for (column, height) in key.heights.iter().enumerate() {
+            let Some(left) = space.checked_sub(*height) else {
+                return vec![0; self.words];
+            };
+            let start = (column * heights + left) * self.words;
+            let bitset = &self.bitsets[start..start + self.words];
+            for (word, bits) in matches.iter_mut().zip(bitset) {
+                *word &= bits;
+            }
+        }
+        matches
+    }
+}
+
+/// Counts the pairs of a key and a lock that fit by comparing every key with every lock.
+/// This is the reference for [`FitIndex`].
+pub fn count_fits_brute_force(keys: &[Key], locks: &[Lock]) -> usize {
+    keys.par_iter()
+        .map(|k| locks.par_iter().filter(|l| k.fits(l)).count())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::tests::schematic;
+    use crate::Schematic;
+    use proptest::prelude::*;
+
+    #[test]
+    fn finds_fitting_locks() {
+        let locks = [
+            Lock(schematic(1, &[0, 5, 3, 4, 3], 7)),
+            Lock(schematic(1, &[1, 2, 0, 5, 3], 7)),
+            Lock(schematic(1, &[5, 5, 5, 5, 5], 7)),
+        ];
+        let index = FitIndex::new(&locks);
+
+        let fitting = Key(schematic(1, &[3, 0, 2, 0, 1], 7));
+        assert_eq!(index.fitting(&fitting).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(index.count(&fitting), 2);
+        assert_eq!(index.count(&Key(schematic(1, &[0, 0, 0, 0, 0], 7))), 3);
+        assert_eq!(index.count(&Key(schematic(1, &[5, 0, 2, 1, 3], 7))), 0);
+        assert_eq!(index.count(&Key(schematic(1, &[0, 0, 0, 0], 7))), 0);
+    }
+
+    #[test]
+    fn empty_index() {
+        let index = FitIndex::new(&[]);
+        assert_eq!(index.count(&Key(schematic(1, &[0, 0, 0, 0, 0], 7))), 0);
+    }
+
+    fn schematics(width: usize, rows: usize) -> impl Strategy<Value = Vec<Schematic>> {
+        let heights = prop::collection::vec(0..rows - 1, width);
+        prop::collection::vec(
+            heights.prop_map(move |heights| schematic(1, &heights, rows)),
+            0..150,
+        )
+    }
+
+    fn keys_and_locks() -> impl Strategy<Value = (Vec<Key>, Vec<Lock>)> {
+        (1..8_usize, 2..9_usize).prop_flat_map(|(width, rows)| {
+            (
+                schematics(width, rows).prop_map(|s| s.into_iter().map(Key).collect()),
+                schematics(width, rows).prop_map(|s| s.into_iter().map(Lock).collect()),
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn index_matches_brute_force((keys, locks) in keys_and_locks()) {
+            let index = FitIndex::new(&locks);
+            for key in &keys {
+                let fitting: Vec<usize> = index.fitting(key).collect();
+                let expected: Vec<usize> = (0..locks.len()).filter(|l| key.fits(&locks[*l])).collect();
+                prop_assert_eq!(fitting, expected);
+            }
+            let count: usize = keys.iter().map(|k| index.count(k)).sum();
+            prop_assert_eq!(count, count_fits_brute_force(&keys, &locks));
+        }
+    }
+}
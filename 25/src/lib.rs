@@ -0,0 +1,427 @@
+use std::fmt::Display;
+
+use aoc_common::{Error, Solution};
+use rayon::prelude::*;
+
+mod index;
+mod pairs;
+mod schematic;
+
+pub use index::{count_fits_brute_force, FitIndex};
+pub use pairs::{fitting_pairs, Overlay, Pair};
+pub use schematic::{Key, Lock, Schematic};
+
+const VECTOR_CAPACITY: usize = 500;
+
+/// Parses the schematics like [`Day25::parse`], but rejects every block that isn't a valid key
+/// or lock instead of skipping or miscounting it. Errors name the block, counted from 1.
+pub fn parse_strict(input: &str) -> Result<(Vec<Key>, Vec<Lock>), Error> {
+    parse_input(input, true)
+}
+
+/// Returns keys and locks. In `strict` mode every block is validated.
+fn parse_input(input: &str, strict: bool) -> Result<(Vec<Key>, Vec<Lock>), Error> {
+    let mut keys: Vec<Key> = Vec::with_capacity(VECTOR_CAPACITY);
+    let mut locks: Vec<Lock> = Vec::with_capacity(VECTOR_CAPACITY);
+    let mut size: Option<(usize, usize)> = None;
+
+    for (index, block_text) in aoc_common::sections(input).into_iter().enumerate() {
+        let block = index + 1;
+        // strict mode reports surrounding whitespace like any other unexpected character
+        let lines: Vec<&str> = aoc_common::lines(block_text)
+            .map(|l| {
+                if strict {
+                    l.trim_end_matches('\r')
+                } else {
+                    l.trim()
+                }
+            })
+            .collect();
+        let expected = |what: String| {
+            if strict {
+                format!("{what} in block {block}")
+            } else {
+                what
+            }
+        };
+
+        if strict {
+            for l in &lines {
+                if let Some(column) = l.find(|c| c != '#' && c != '.') {
+                    return Err(Error::parse(
+                        input,
+                        &l[column..],
+                        expected("`#` or `.`".into()),
+                    ));
+                }
+            }
+        }
+
+        let header = lines[0];
+        // locks hang from a full top row, keys stand on a full bottom row
+        let is_lock = if header.bytes().all(|b| b == b'#') {
+            true
+        } else if header.bytes().all(|b| b == b'.') {
+            false
+        } else if strict {
+            return Err(Error::parse(
+                input,
+                header,
+                expected("a full or an empty row".into()),
+            ));
+        } else {
+            continue;
+        };
+
+        let width = header.len();
+        for l in &lines[1..] {
+            if l.len() != width {
+                return Err(Error::parse(
+                    input,
+                    l,
+                    expected(format!("a row of {width} pins")),
+                ));
+            }
+        }
+        if strict {
+            validate_pins(&lines, is_lock)
+                .map_err(|(text, what)| Error::parse(input, text, expected(what.to_string())))?;
+        }
+
+        let rows = lines.len();
+        let mut heights = vec![0; width];
+        // the last row is the full row of the other kind
+        for l in lines.iter().take(rows - 1).skip(1) {
+            for (height, pin) in heights.iter_mut().zip(l.bytes()) {
+                *height += usize::from(pin == b'#');
+            }
+        }
+
+        match size {
+            Some((w, r)) if (w, r) != (width, rows) => {
+                return Err(Error::parse(
+                    input,
+                    header,
+                    expected(format!(
+                        "a schematic of {w} pins and {r} rows like the first one"
+                    )),
+                ));
+            }
+            _ => size = Some((width, rows)),
+        }
+
+        let schematic = Schematic {
+            block,
+            heights,
+            rows,
+        };
+        if is_lock {
+            locks.push(Lock(schematic));
+        } else {
+            keys.push(Key(schematic));
+        }
+    }
+
+    Ok((keys, locks))
+}
+
+/// Checks that the row opposite the full row is empty and that every pin is one piece
+/// attached to the full row. Returns the offending text and what was expected.
+fn validate_pins<'a>(lines: &[&'a str], top_full: bool) -> Result<(), (&'a str, &'static str)> {
+    let last = lines[lines.len() - 1];
+    if top_full && last.contains('#') {
+        return Err((last, "an empty row at the bottom"));
+    }
+    if !top_full && last.contains('.') {
+        return Err((last, "a full row at the bottom"));
+    }
+
+    for pair in lines.windows(2) {
+        // the pin cells of the row farther from the full row need a pin cell next to them
+        let (near, far) = if top_full {
+            (pair[0], pair[1])
+        } else {
+            (pair[1], pair[0])
+        };
+        let gap = near
+            .bytes()
+            .zip(far.bytes())
+            .position(|(near, far)| near == b'.' && far == b'#');
+        if let Some(column) = gap {
+            return Err((&far[column..], "pins without gaps"));
+        }
+    }
+    Ok(())
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    /// The pin heights of all keys and locks
+    type Input = (Vec<Key>, Vec<Lock>);
+
+    const PARTS: u8 = 1;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_input(input, false)
+    }
+
+    fn part1((keys, locks): &Self::Input) -> Result<impl Display, Error> {
+        let index = FitIndex::new(locks);
+        let part1: usize = keys.par_iter().map(|k| index.count(k)).sum();
+
+        Ok(part1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example_lock() {
+        let input = "#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....";
+        let (keys, locks) = parse_input(input, false).unwrap();
+        assert!(keys.is_empty());
+        assert_eq!(locks[0].0.heights, vec![0, 5, 3, 4, 3]);
+        assert_eq!(locks[0].0.rows, 7);
+    }
+
+    #[test]
+    fn example_key() {
+        let input = ".....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####";
+
+        let (keys, locks) = parse_input(input, false).unwrap();
+        assert!(locks.is_empty());
+        assert_eq!(keys[0].0.heights, vec![5, 0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn example_fit_count() {
+        let input = "#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####";
+
+        let (keys, locks) = parse_input(input, false).unwrap();
+        let heights = |s: &Schematic| s.heights.clone();
+        assert_eq!(
+            locks.iter().map(|l| heights(&l.0)).collect::<Vec<_>>(),
+            [[0, 5, 3, 4, 3], [1, 2, 0, 5, 3]]
+        );
+        assert_eq!(
+            keys.iter().map(|k| heights(&k.0)).collect::<Vec<_>>(),
+            [[5, 0, 2, 1, 3], [4, 3, 4, 0, 2], [3, 0, 2, 0, 1]]
+        );
+        assert_eq!(count_fits_brute_force(&keys, &locks), 3);
+        assert_eq!(Day25::part1(&(keys, locks)).unwrap().to_string(), "3");
+    }
+
+    #[test]
+    fn short_row() {
+        let input = "#####
+.####
+.###
+.....";
+
+        assert_eq!(
+            parse_input(input, false).unwrap_err().to_string(),
+            "line 3, column 1: expected a row of 5 pins, found `.###`"
+        );
+    }
+
+    #[test]
+    fn any_size() {
+        let input = "###
+#.#
+...
+...
+
+...
+..#
+#.#
+###";
+        let (keys, locks) = parse_input(input, false).unwrap();
+        assert_eq!(locks[0].0.heights, vec![1, 0, 1]);
+        assert_eq!(keys[0].0.heights, vec![1, 0, 2]);
+        assert_eq!(count_fits_brute_force(&keys, &locks), 0);
+        assert_eq!(Day25::part1(&(keys, locks)).unwrap().to_string(), "0");
+    }
+
+    #[test]
+    fn mixed_sizes() {
+        let input = "#####
+.....
+.....
+
+......
+......
+######";
+
+        assert_eq!(
+            parse_input(input, false).unwrap_err().to_string(),
+            "line 5, column 1: expected a schematic of 5 pins and 3 rows like the first one, \
+             found `......`"
+        );
+    }
+
+    const VALID: &str = "#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####";
+
+    fn strict_error(input: &str) -> String {
+        parse_strict(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn strict_accepts_valid_schematics() {
+        assert_eq!(parse_strict(VALID), parse_input(VALID, false));
+    }
+
+    #[test]
+    fn strict_rejects_unknown_characters() {
+        let input = VALID.replacen("#.#.#", "#.o.#", 1);
+        assert_eq!(
+            strict_error(&input),
+            "line 13, column 3: expected `#` or `.` in block 2, found `o.#`"
+        );
+    }
+
+    #[test]
+    fn strict_rejects_surrounding_whitespace() {
+        assert_eq!(
+            strict_error("  #####\n  .####\n  .....\n"),
+            "line 1, column 1: expected `#` or `.` in block 1, found `  #####`"
+        );
+        let input = VALID.replacen("#.#.#", "#.#.# ", 1);
+        assert_eq!(
+            strict_error(&input),
+            "line 13, column 6: expected `#` or `.` in block 2, found ` `"
+        );
+        assert_eq!(
+            parse_input("  #####\n  .####\n  .....\n", false)
+                .unwrap()
+                .1
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn strict_rejects_ragged_rows() {
+        let input = VALID.replacen(".#...", ".#..", 1);
+        assert_eq!(
+            strict_error(&input),
+            "line 6, column 1: expected a row of 5 pins in block 1, found `.#..`"
+        );
+    }
+
+    #[test]
+    fn strict_rejects_unknown_header() {
+        let input = VALID.replacen("#####", "##.##", 1);
+        assert_eq!(
+            strict_error(&input),
+            "line 1, column 1: expected a full or an empty row in block 1, found `##.##`"
+        );
+        assert!(parse_input(&input, false).unwrap().1.is_empty());
+    }
+
+    #[test]
+    fn strict_rejects_pins_with_gaps() {
+        let input = VALID.replacen(".#...\n.....", ".#..#\n.....", 1);
+        assert_eq!(
+            strict_error(&input),
+            "line 6, column 5: expected pins without gaps in block 1, found `#`"
+        );
+
+        let input = VALID.replacen("#....\n#...#", "#...#\n#....", 1);
+        assert_eq!(
+            strict_error(&input),
+            "line 11, column 5: expected pins without gaps in block 2, found `#`"
+        );
+    }
+
+    #[test]
+    fn strict_rejects_missing_full_or_empty_row() {
+        let input = VALID.replacen(".#...\n.....", ".#...\n.#...", 1);
+        assert_eq!(
+            strict_error(&input),
+            "line 7, column 1: expected an empty row at the bottom in block 1, found `.#...`"
+        );
+
+        let input = VALID.replacen("#.###\n#####", "#.###\n#.###", 1);
+        assert_eq!(
+            strict_error(&input),
+            "line 15, column 1: expected a full row at the bottom in block 2, found `#.###`"
+        );
+    }
+
+    #[test]
+    fn strict_rejects_wrong_height() {
+        let input = VALID.replacen("#....\n#....\n", "#....\n", 1);
+        assert_eq!(
+            strict_error(&input),
+            "line 9, column 1: expected a schematic of 5 pins and 7 rows like the first one in \
+             block 2, found `.....`"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{FitIndex, Key, Lock};
+
+/// A key and a lock that fit, identified by the numbers of their blocks in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Pair {
+    pub key: usize,
+    pub lock: usize,
+}
+
+/// A key and a lock drawn on top of each other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Overlay {
+    pub key: usize,
+    pub lock: usize,
+    /// Whether no column overlaps.
+    pub fits: bool,
+    /// The free rows between the pins of each column, negative if the pins overlap.
+    pub clearance: Vec<isize>,
+    /// The combined block: `#` for the pins of the lock, `O` for those of the key, `X` where
+    /// they overlap and `.` for free space.
+    pub rows: Vec<String>,
+}
+
+/// Returns all pairs of a key and a lock that fit, ordered by key and then by lock.
+pub fn fitting_pairs(keys: &[Key], locks: &[Lock]) -> Vec<Pair> {
+    let index = FitIndex::new(locks);
+    keys.iter()
+        .flat_map(|key| {
+            index.fitting(key).map(|lock| Pair {
+                key: key.0.block,
+                lock: locks[lock].0.block,
+            })
+        })
+        .collect()
+}
+
+impl Overlay {
+    /// Draws the lock hanging from the top row and the key standing on the bottom row.
+    /// Both have to have the same size.
+    pub fn new(Key(key): &Key, Lock(lock): &Lock) -> Self {
+        let rows = lock.rows;
+        let clearance = key
+            .heights
+            .iter()
+            .zip(&lock.heights)
+            .map(|(k, l)| lock.space() as isize - (k + l) as isize)
+            .collect();
+
+        let drawn = (0..rows)
+            .map(|row| {
+                lock.heights
+                    .iter()
+                    .zip(&key.heights)
+                    .map(|(l, k)| match (row <= *l, row + 1 + k >= rows) {
+                        (true, true) => 'X',
+                        (true, false) => '#',
+                        (false, true) => 'O',
+                        (false, false) => '.',
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Overlay {
+            key: key.block,
+            lock: lock.block,
+            fits: key.fits(lock),
+            clearance,
+            rows: drawn,
+        }
+    }
+}
+
+impl fmt::Display for Pair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key {}, lock {}", self.key, self.lock)
+    }
+}
+
+/// Writes the combined block followed by the clearance of each column.
+impl fmt::Display for Overlay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.fits { "fit" } else { "overlap" };
+        writeln!(f, "key {}, lock {}: {verdict}", self.key, self.lock)?;
+        for row in &self.rows {
+            writeln!(f, "{row}")?;
+        }
+        let clearance: Vec<String> = self.clearance.iter().map(|c| c.to_string()).collect();
+        writeln!(f, "clearance: {}", clearance.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::tests::schematic;
+
+    #[test]
+    fn lists_fitting_pairs_by_block() {
+        let locks = [
+            Lock(schematic(1, &[0, 5, 3, 4, 3], 7)),
+            Lock(schematic(2, &[1, 2, 0, 5, 3], 7)),
+        ];
+        let keys = [
+            Key(schematic(3, &[5, 0, 2, 1, 3], 7)),
+            Key(schematic(4, &[4, 3, 4, 0, 2], 7)),
+            Key(schematic(5, &[3, 0, 2, 0, 1], 7)),
+        ];
+
+        let pairs: Vec<String> = fitting_pairs(&keys, &locks)
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(pairs, ["key 4, lock 2", "key 5, lock 1", "key 5, lock 2"]);
+    }
+
+    #[test]
+    fn draws_clearance() {
+        let overlay = Overlay::new(
+            &Key(schematic(4, &[4, 3, 4, 0, 2], 7)),
+            &Lock(schematic(2, &[1, 2, 0, 5, 3], 7)),
+        );
+
+        assert_eq!(
+            overlay.to_string(),
+            "key 4, lock 2: fit\n\
+             #####\n\
+             ##.##\n\
+             O#O##\n\
+             OOO##\n\
+             OOO#O\n\
+             OOO#O\n\
+             OOOOO\n\
+             clearance: 0 0 1 0 0\n"
+        );
+    }
+
+    #[test]
+    fn draws_overlapping_pins() {
+        let overlay = Overlay::new(
+            &Key(schematic(3, &[5, 0, 2, 1, 3], 7)),
+            &Lock(schematic(1, &[0, 5, 3, 4, 3], 7)),
+        );
+
+        assert!(!overlay.fits);
+        assert_eq!(overlay.clearance, vec![0, 0, 0, 0, -1]);
+        assert_eq!(overlay.rows[3], "O###X");
+    }
+
+    #[test]
+    fn serializes_to_json() {
+        let overlay = Overlay::new(&Key(schematic(2, &[1], 7)), &Lock(schematic(1, &[0], 7)));
+        assert_eq!(
+            serde_json::to_string(&overlay).unwrap(),
+            r##"{"key":2,"lock":1,"fits":true,"clearance":[4],"rows":["#",".",".",".",".","O","O"]}"##
+        );
+    }
+}
@@ -0,0 +1,81 @@
+/// The pin heights of a key or lock of any size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    /// The position of the block in the input, starting at 1.
+    pub block: usize,
+    /// The height of each pin, not counting the full row it hangs from or stands on.
+    pub heights: Vec<usize>,
+    /// The number of rows of the block, including the full row and the empty row.
+    pub rows: usize,
+}
+
+impl Schematic {
+    /// Returns the number of pins.
+    pub fn width(&self) -> usize {
+        self.heights.len()
+    }
+
+    /// Returns the space between the full rows of a key and a lock that are put together.
+    pub fn space(&self) -> usize {
+        self.rows.saturating_sub(2)
+    }
+
+    /// Checks whether the pins of both schematics fit next to each other without overlapping
+    /// in any column. Schematics of different sizes never fit.
+    pub fn fits(&self, other: &Schematic) -> bool {
+        self.width() == other.width()
+            && self.rows == other.rows
+            && self
+                .heights
+                .iter()
+                .zip(&other.heights)
+                .all(|(a, b)| a + b <= self.space())
+    }
+}
+
+/// A lock: pins hanging down from a full top row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lock(pub Schematic);
+
+/// A key: pins standing up on a full bottom row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Key(pub Schematic);
+
+impl Key {
+    /// Checks whether the key fits into the lock without any pins overlapping.
+    pub fn fits(&self, lock: &Lock) -> bool {
+        self.0.fits(&lock.0)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Builds the schematic of the given block with the pin heights and number of rows.
+    pub(crate) fn schematic(block: usize, heights: &[usize], rows: usize) -> Schematic {
+        Schematic {
+            block,
+            heights: heights.to_vec(),
+            rows,
+        }
+    }
+
+    #[test]
+    fn fits_when_no_column_overlaps() {
+        let lock = schematic(1, &[0, 5, 3, 4, 3], 7);
+        assert!(lock.fits(&schematic(1, &[3, 0, 2, 0, 1], 7)));
+        assert!(!lock.fits(&schematic(1, &[5, 0, 2, 1, 3], 7)));
+
+        let wide = schematic(1, &[1, 0, 2, 3, 0, 1, 4], 6);
+        assert!(wide.fits(&schematic(1, &[3, 4, 2, 1, 4, 3, 0], 6)));
+        assert!(!wide.fits(&schematic(1, &[3, 4, 2, 2, 4, 3, 0], 6)));
+    }
+
+    #[test]
+    fn different_sizes_never_fit() {
+        let lock = schematic(1, &[0, 0, 0], 5);
+        assert!(!lock.fits(&schematic(1, &[0, 0, 0], 6)));
+        assert!(!lock.fits(&schematic(1, &[0, 0, 0, 0], 5)));
+    }
+}
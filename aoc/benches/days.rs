@@ -0,0 +1,95 @@
+//! Benchmarks of parsing and solving every day on large generated inputs.
+//!
+//! Save a baseline with `cargo bench -p aoc -- --save-baseline <name>` and compare a later run
+//! against it with `cargo bench -p aoc -- --baseline <name>`.
+
+use std::hint::black_box;
+
+use aoc_common::Solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+mod generate;
+
+use generate::Rng;
+
+/// Benchmarks parsing the input and each part of a day separately.
+fn bench_day<S: Solution>(c: &mut Criterion, name: &str, input: &str) {
+    let mut group = c.benchmark_group(name);
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(input))));
+
+    let parsed = match S::parse(input) {
+        Ok(parsed) => parsed,
+        Err(e) => panic!("Failed to parse generated input of {name}: {e}"),
+    };
+    for part in 1..=S::PARTS {
+        group.bench_function(format!("part{part}"), |b| {
+            b.iter(|| aoc_common::solve::<S>(black_box(&parsed), part))
+        });
+    }
+
+    group.finish();
+}
+
+/// Compares evaluating 64 input vectors of the day 24 adder one by one with a single
+/// bit-parallel pass over the compiled circuit.
+fn day24_simulation(c: &mut Criterion, input: &str) {
+    let puzzle: aoc_2024_24::Puzzle = input.parse().expect("generated circuit");
+    let circuit = puzzle.compile().expect("generated circuit");
+    let mut group = c.benchmark_group("day24_simulation");
+
+    group.bench_function("64_evaluations", |b| {
+        b.iter(|| {
+            for _ in 0..aoc_2024_24::LANES {
+                black_box(puzzle.evaluate().ok());
+            }
+        })
+    });
+    group.bench_function("compiled_pass", |b| {
+        b.iter(|| {
+            let mut values = circuit.values();
+            circuit.evaluate(black_box(&mut values));
+            values
+        })
+    });
+
+    group.finish();
+}
+
+/// Compares counting the fitting day 25 key/lock pairs by comparing every key with every lock
+/// with counting them through the per-column bitsets of the fit index.
+fn day25_fit(c: &mut Criterion, input: &str) {
+    let (keys, locks) = aoc_2024_25::Day25::parse(input).expect("generated schematics");
+    let mut group = c.benchmark_group("day25_fit");
+
+    group.bench_function("brute_force", |b| {
+        b.iter(|| aoc_2024_25::count_fits_brute_force(black_box(&keys), black_box(&locks)))
+    });
+    group.bench_function("index", |b| {
+        b.iter(|| {
+            let index = aoc_2024_25::FitIndex::new(black_box(&locks));
+            keys.iter().map(|k| index.count(k)).sum::<usize>()
+        })
+    });
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    let mut rng = Rng::new(2024);
+
+    bench_day::<aoc_2024_01::Day01>(c, "day01", &generate::day01(&mut rng, 10_000));
+    bench_day::<aoc_2024_02::Day02>(c, "day02", &generate::day02(&mut rng, 10_000));
+    bench_day::<aoc_2024_03::Day03>(c, "day03", &generate::day03(&mut rng, 50_000));
+    bench_day::<aoc_2024_04::Day04>(c, "day04", &generate::day04(&mut rng, 500));
+    bench_day::<aoc_2024_05::Day05>(c, "day05", &generate::day05(&mut rng, 90, 2_000));
+    let day24 = generate::day24(&mut rng, 63);
+    bench_day::<aoc_2024_24::Day24>(c, "day24", &day24);
+    day24_simulation(c, &day24);
+    let day25 = generate::day25(&mut rng, 4_000);
+    bench_day::<aoc_2024_25::Day25>(c, "day25", &day25);
+    day25_fit(c, &day25);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,145 @@
+//! Generators for large puzzle inputs in the format of each day.
+//!
+//! All generators are deterministic, so runs can be compared against a saved baseline.
+
+use aoc_2024_24::Puzzle;
+pub use aoc_common::Rng;
+
+/// Two columns of location IDs.
+pub fn day01(rng: &mut Rng, lines: usize) -> String {
+    (0..lines)
+        .map(|_| {
+            format!(
+                "{}   {}\n",
+                10_000 + rng.below(90_000),
+                10_000 + rng.below(90_000)
+            )
+        })
+        .collect()
+}
+
+/// Reports of 5 to 8 levels, mostly monotonic with small steps.
+pub fn day02(rng: &mut Rng, lines: usize) -> String {
+    (0..lines)
+        .map(|_| {
+            let len = 5 + rng.below(4);
+            let descending = rng.below(2) == 0;
+            let mut level = 40 + rng.below(20) as i64;
+            let mut levels = Vec::new();
+            for _ in 0..len {
+                levels.push(level.to_string());
+                let step = rng.below(4) as i64;
+                level += if descending { -step } else { step };
+            }
+            levels.join(" ") + "\n"
+        })
+        .collect()
+}
+
+/// Corrupted memory with `mul` instructions, conditionals and noise.
+pub fn day03(rng: &mut Rng, tokens: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..tokens {
+        match rng.below(10) {
+            0 => input.push_str("do()"),
+            1 => input.push_str("don't()"),
+            2..=5 => input.push_str(&format!("mul({},{})", rng.below(1000), rng.below(1000))),
+            _ => input.push_str(
+                ["mul(", "]#", "what()", "mul[3,4]", "from(", ")", " "][rng.below(7) as usize],
+            ),
+        }
+    }
+    input.push('\n');
+    input
+}
+
+/// Square word search of the given size.
+pub fn day04(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut line: String = (0..size)
+                .map(|_| ['X', 'M', 'A', 'S'][rng.below(4) as usize])
+                .collect();
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+/// A total order of `pages` page numbers as rules and updates using random subsets of them.
+pub fn day05(rng: &mut Rng, pages: usize, updates: usize) -> String {
+    let mut order: Vec<usize> = (10..10 + pages).collect();
+    rng.shuffle(&mut order);
+
+    let mut input = String::new();
+    for (i, before) in order.iter().enumerate() {
+        for after in &order[i + 1..] {
+            input.push_str(&format!("{before}|{after}\n"));
+        }
+    }
+    input.push('\n');
+
+    for _ in 0..updates {
+        let mut update = order.clone();
+        rng.shuffle(&mut update);
+        let len = 5 + 2 * rng.below(10) as usize;
+        let update: Vec<String> = update[..len.min(pages)]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        input.push_str(&update.join(","));
+        input.push('\n');
+    }
+    input
+}
+
+/// A ripple-carry adder with random inputs, shuffled gates and four swapped pairs of gate
+/// outputs.
+pub fn day24(rng: &mut Rng, bits: usize) -> String {
+    let swaps = [
+        ("z05".to_string(), "co05".to_string()),
+        (format!("hs{:02}", bits / 4), format!("hc{:02}", bits / 4)),
+        (format!("z{:02}", bits / 2), format!("cd{:02}", bits / 2)),
+        (format!("z{:02}", bits - 3), format!("co{:02}", bits - 3)),
+    ];
+    let swaps: Vec<(&str, &str)> = swaps
+        .iter()
+        .map(|(a, b)| (a.as_str(), b.as_str()))
+        .collect();
+
+    let mut puzzle = Puzzle::ripple_carry_adder(bits, &swaps);
+    for bus in ["x", "y"] {
+        for bit in 0..bits {
+            puzzle.set_initial(&format!("{bus}{bit:02}"), rng.below(2) as u8);
+        }
+    }
+    rng.shuffle(&mut puzzle.queue);
+    puzzle.to_string()
+}
+
+/// Random keys and locks with five pins each.
+pub fn day25(rng: &mut Rng, schematics: usize) -> String {
+    let mut blocks = Vec::new();
+    for i in 0..schematics {
+        let is_lock = i % 2 == 0;
+        let heights: Vec<usize> = (0..5).map(|_| rng.below(6) as usize).collect();
+
+        let mut rows = Vec::new();
+        for row in 0..7 {
+            let line: String = heights
+                .iter()
+                .map(|h| {
+                    let filled = if is_lock { row <= *h } else { 6 - row <= *h };
+                    if filled {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            rows.push(line);
+        }
+        blocks.push(rows.join("\n"));
+    }
+    blocks.join("\n\n") + "\n"
+}
@@ -0,0 +1,253 @@
+//! Tools for inspecting the circuits of day 24.
+
+use std::path::PathBuf;
+
+use aoc_2024_24::{BusOperation, Equivalence, GraphFormat, Puzzle};
+use clap::{Subcommand, ValueEnum};
+
+use crate::{to_json, OutputFormat};
+
+#[derive(Subcommand)]
+pub enum CircuitCommand {
+    /// Parses the circuit and writes it back in the puzzle format, sorting the initial values
+    Print {
+        /// Reads the circuit from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Exports the gate network as a graph
+    Export {
+        #[arg(long, value_enum, default_value_t = Format::Dot)]
+        format: Format,
+        /// Highlights everything the output bit with this index depends on
+        #[arg(long)]
+        highlight: Option<usize>,
+        /// Reads the circuit from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Classifies the gates by their role in a ripple-carry adder and lists the gates that
+    /// fit no role
+    Analyze {
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Reads the circuit from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Evaluates the circuit and prints every gate evaluation with its inputs, output and
+    /// pass; JSON is written as one step per line
+    Trace {
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Only traces the gates this wire depends on
+        #[arg(long)]
+        output: Option<String>,
+        /// Reads the circuit from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Lists the wires a wire transitively depends on, grouped by bus
+    Cone {
+        /// The wire to start from
+        wire: String,
+        /// Lists the wires that depend on the wire instead
+        #[arg(long)]
+        fan_out: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Reads the circuit from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Prints the number of gates on the longest path to every z output
+    Depth {
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Reads the circuit from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Simulates the circuit with every value of the x and y buses, or random values for wide
+    /// circuits, and reports the lowest z output that differs from the operation
+    Verify {
+        /// The operation on the x and y buses the circuit should compute
+        #[arg(long, value_enum, default_value_t = Op::Add)]
+        op: Op,
+        /// The number of random values to simulate for wide circuits
+        #[arg(long, default_value_t = 10_000, value_parser = clap::value_parser!(u64).range(1..))]
+        samples: u64,
+        /// The seed of the random values
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Reads the circuit from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Checks that the z outputs match a reference for every value of the initial wires
+    Check {
+        /// Compares against the circuit in this file instead of an operation
+        #[arg(long, conflicts_with = "op")]
+        against: Option<PathBuf>,
+        /// The operation on the x and y buses the circuit should compute
+        #[arg(long, value_enum, default_value_t = Op::Add)]
+        op: Op,
+        /// Reads the circuit from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Op {
+    Add,
+    And,
+    Or,
+    Xor,
+}
+
+impl From<Op> for BusOperation {
+    fn from(value: Op) -> Self {
+        match value {
+            Op::Add => BusOperation::Add,
+            Op::And => BusOperation::And,
+            Op::Or => BusOperation::Or,
+            Op::Xor => BusOperation::Xor,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Dot,
+    Mermaid,
+}
+
+impl From<Format> for GraphFormat {
+    fn from(value: Format) -> Self {
+        match value {
+            Format::Dot => GraphFormat::Dot,
+            Format::Mermaid => GraphFormat::Mermaid,
+        }
+    }
+}
+
+fn parse_circuit(input: Option<PathBuf>) -> Result<Puzzle, String> {
+    let input = crate::read_input_arg(input)?;
+    input
+        .parse()
+        .map_err(|e| format!("Failed to parse circuit: {e}"))
+}
+
+/// Runs a circuit command and prints its result.
+pub fn run(command: CircuitCommand) -> Result<(), String> {
+    match command {
+        CircuitCommand::Print { input } => {
+            let puzzle = parse_circuit(input)?;
+            print!("{puzzle}");
+            Ok(())
+        }
+        CircuitCommand::Export {
+            format,
+            highlight,
+            input,
+        } => {
+            let puzzle = parse_circuit(input)?;
+            print!("{}", puzzle.export_graph(format.into(), highlight));
+            Ok(())
+        }
+        CircuitCommand::Analyze { format, input } => {
+            let analysis = parse_circuit(input)?.analyze_adder();
+            match format {
+                OutputFormat::Text => print!("{analysis}"),
+                OutputFormat::Json => println!("{}", to_json(&analysis)?),
+            }
+            Ok(())
+        }
+        CircuitCommand::Trace {
+            format,
+            output,
+            input,
+        } => {
+            let trace = parse_circuit(input)?
+                .trace(output.as_deref())
+                .map_err(|e| format!("Failed to trace circuit: {e}"))?;
+            match format {
+                OutputFormat::Text => print!("{trace}"),
+                OutputFormat::Json => {
+                    for step in &trace.steps {
+                        let line = serde_json::to_string(step)
+                            .map_err(|e| format!("Failed to write JSON: {e}"))?;
+                        println!("{line}");
+                    }
+                }
+            }
+            Ok(())
+        }
+        CircuitCommand::Cone {
+            wire,
+            fan_out,
+            format,
+            input,
+        } => {
+            let puzzle = parse_circuit(input)?;
+            let groups = if fan_out {
+                puzzle.fan_out(&wire)
+            } else {
+                puzzle.fan_in(&wire)
+            }
+            .map_err(|e| format!("Failed to query circuit: {e}"))?;
+            match format {
+                OutputFormat::Text => print!("{groups}"),
+                OutputFormat::Json => println!("{}", to_json(&groups)?),
+            }
+            Ok(())
+        }
+        CircuitCommand::Depth { format, input } => {
+            let summary = parse_circuit(input)?
+                .output_depths()
+                .map_err(|e| format!("Failed to query circuit: {e}"))?;
+            match format {
+                OutputFormat::Text => print!("{summary}"),
+                OutputFormat::Json => println!("{}", to_json(&summary)?),
+            }
+            Ok(())
+        }
+        CircuitCommand::Verify {
+            op,
+            samples,
+            seed,
+            input,
+        } => {
+            let verification = parse_circuit(input)?
+                .verify(op.into(), samples, seed)
+                .map_err(|e| format!("Failed to verify circuit: {e}"))?;
+            print!("{verification}");
+            match verification.failing_bit {
+                Some(bit) => Err(format!("Output z{bit:02} is wrong")),
+                None => Ok(()),
+            }
+        }
+        CircuitCommand::Check { against, op, input } => {
+            let puzzle = parse_circuit(input)?;
+            let result = match against {
+                Some(path) => {
+                    let reference = crate::read_input(&path)?
+                        .parse::<Puzzle>()
+                        .map_err(|e| format!("Failed to parse reference circuit: {e}"))?;
+                    puzzle.equivalent_to(&reference)
+                }
+                None => puzzle.computes(op.into()),
+            };
+            match result.map_err(|e| format!("Failed to check circuit: {e}"))? {
+                Equivalence::Equivalent => {
+                    println!("Equivalent");
+                    Ok(())
+                }
+                Equivalence::Different(counterexample) => {
+                    Err(format!("Not equivalent: {counterexample}"))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use aoc_common::Solution;
+use clap::{Parser, Subcommand, ValueEnum};
+
+mod circuit;
+mod schematics;
+
+/// Runs the selected part (or all parts) of a day on the given input.
+/// Returns `false` if the input couldn't be parsed or a part failed.
+type RunFn = fn(u8, Option<u8>, &str) -> bool;
+
+struct Day {
+    day: u8,
+    run: RunFn,
+}
+
+const DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        run: run_day::<aoc_2024_01::Day01>,
+    },
+    Day {
+        day: 2,
+        run: run_day::<aoc_2024_02::Day02>,
+    },
+    Day {
+        day: 3,
+        run: run_day::<aoc_2024_03::Day03>,
+    },
+    Day {
+        day: 4,
+        run: run_day::<aoc_2024_04::Day04>,
+    },
+    Day {
+        day: 5,
+        run: run_day::<aoc_2024_05::Day05>,
+    },
+    Day {
+        day: 24,
+        run: run_day::<aoc_2024_24::Day24>,
+    },
+    Day {
+        day: 25,
+        run: run_day::<aoc_2024_25::Day25>,
+    },
+];
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs one or both parts of a single day
+    Run {
+        #[arg(long)]
+        day: u8,
+        /// Runs only the given part instead of both
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Reads the puzzle input from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Runs every implemented day, reading the input of day N from `<INPUTS>/NN.txt`
+    All {
+        #[arg(long, default_value = "inputs")]
+        inputs: PathBuf,
+    },
+    /// Inspects the circuits of day 24
+    #[command(subcommand)]
+    Circuit(circuit::CircuitCommand),
+    /// Inspects the keys and locks of day 25
+    #[command(subcommand)]
+    Schematics(schematics::SchematicsCommand),
+}
+
+/// The output formats of the commands that inspect a day's input.
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+fn to_json(value: &impl serde::Serialize) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|e| format!("Failed to write JSON: {e}"))
+}
+
+fn read_input(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read input data from {}: {e}", path.display()))
+}
+
+/// Reads the input from the given file, or from stdin if there is none.
+fn read_input_arg(path: Option<PathBuf>) -> Result<String, String> {
+    match path {
+        Some(path) => read_input(&path),
+        None => Ok(aoc_common::read_input()),
+    }
+}
+
+/// Parses the input and runs the selected parts of a day, printing their answers with timings.
+fn run_day<S: Solution>(day: u8, part: Option<u8>, input: &str) -> bool {
+    let start = Instant::now();
+    let input = match S::parse(input) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Day {day:02} failed to parse input: {e}");
+            return false;
+        }
+    };
+    println!("Day {day:02} parse: ({:.2?})", start.elapsed());
+
+    let parts = match part {
+        Some(part) => part..=part,
+        None => 1..=S::PARTS,
+    };
+
+    let mut success = true;
+    for part in parts {
+        let start = Instant::now();
+        let answer = aoc_common::solve::<S>(&input, part);
+        let elapsed = start.elapsed();
+
+        match answer {
+            Ok(answer) => println!("Day {day:02} part {part}: {answer} ({elapsed:.2?})"),
+            Err(e) => {
+                eprintln!("Day {day:02} part {part} failed: {e}");
+                success = false;
+            }
+        }
+    }
+
+    success
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let success = match cli.command {
+        Command::Run { day, part, input } => {
+            let Some(day) = DAYS.iter().find(|d| d.day == day) else {
+                eprintln!("Day {day} is not implemented");
+                return ExitCode::FAILURE;
+            };
+
+            match read_input_arg(input) {
+                Ok(input) => (day.run)(day.day, part, &input),
+                Err(e) => {
+                    eprintln!("{e}");
+                    false
+                }
+            }
+        }
+        Command::All { inputs } => {
+            let start = Instant::now();
+            let mut success = true;
+
+            for day in DAYS {
+                let path = inputs.join(format!("{:02}.txt", day.day));
+                match read_input(&path) {
+                    Ok(input) => success &= (day.run)(day.day, None, &input),
+                    Err(e) => {
+                        eprintln!("{e}");
+                        success = false;
+                    }
+                }
+            }
+
+            println!("Total: {:.2?}", start.elapsed());
+            success
+        }
+        Command::Circuit(command) => match circuit::run(command) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("{e}");
+                false
+            }
+        },
+        Command::Schematics(command) => match schematics::run(command) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("{e}");
+                false
+            }
+        },
+    };
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,92 @@
+//! Tools for inspecting the keys and locks of day 25.
+
+use std::path::PathBuf;
+
+use aoc_2024_25::{Day25, Key, Lock, Overlay};
+use aoc_common::Solution;
+use clap::Subcommand;
+
+use crate::{to_json, OutputFormat};
+
+#[derive(Subcommand)]
+pub enum SchematicsCommand {
+    /// Checks that every block is a key or lock with straight pins and the same size
+    Validate {
+        /// Reads the schematics from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Lists every key and lock that fit, by the numbers of their blocks in the input
+    Pairs {
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Reads the schematics from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Draws a key and a lock on top of each other with the clearance of each column
+    Show {
+        /// The number of the key's block in the input, starting at 1
+        key: usize,
+        /// The number of the lock's block in the input, starting at 1
+        lock: usize,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Reads the schematics from this file instead of stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+}
+
+fn parse_schematics(input: Option<PathBuf>) -> Result<(Vec<Key>, Vec<Lock>), String> {
+    let input = crate::read_input_arg(input)?;
+    Day25::parse(&input).map_err(|e| format!("Failed to parse schematics: {e}"))
+}
+
+/// Runs a schematics command and prints its result.
+pub fn run(command: SchematicsCommand) -> Result<(), String> {
+    match command {
+        SchematicsCommand::Validate { input } => {
+            let input = crate::read_input_arg(input)?;
+            let (keys, locks) = aoc_2024_25::parse_strict(&input)
+                .map_err(|e| format!("Invalid schematics: {e}"))?;
+            println!("{} keys and {} locks are valid", keys.len(), locks.len());
+            Ok(())
+        }
+        SchematicsCommand::Pairs { format, input } => {
+            let (keys, locks) = parse_schematics(input)?;
+            let pairs = aoc_2024_25::fitting_pairs(&keys, &locks);
+            match format {
+                OutputFormat::Text => {
+                    for pair in &pairs {
+                        println!("{pair}");
+                    }
+                }
+                OutputFormat::Json => println!("{}", to_json(&pairs)?),
+            }
+            Ok(())
+        }
+        SchematicsCommand::Show {
+            key,
+            lock,
+            format,
+            input,
+        } => {
+            let (keys, locks) = parse_schematics(input)?;
+            let key = keys
+                .iter()
+                .find(|k| k.0.block == key)
+                .ok_or_else(|| format!("Block {key} is not a key"))?;
+            let lock = locks
+                .iter()
+                .find(|l| l.0.block == lock)
+                .ok_or_else(|| format!("Block {lock} is not a lock"))?;
+            let overlay = Overlay::new(key, lock);
+            match format {
+                OutputFormat::Text => print!("{overlay}"),
+                OutputFormat::Json => println!("{}", to_json(&overlay)?),
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Runs every day on the example input published with its puzzle and compares the answers.
+//!
+//! The inputs live in `tests/examples/NN.txt`. Parts whose example differs from the first
+//! part's one use `tests/examples/NN-2.txt` instead.
+
+use aoc_common::Solution;
+
+/// Parses `input` and checks the answer of `part` against `expected`.
+fn check<S: Solution>(input: &str, part: u8, expected: &str) {
+    let parsed = match S::parse(input) {
+        Ok(parsed) => parsed,
+        Err(e) => panic!("Failed to parse example input: {e}"),
+    };
+
+    match aoc_common::solve::<S>(&parsed, part) {
+        Ok(answer) => assert_eq!(answer, expected, "wrong answer for part {part}"),
+        Err(e) => panic!("Failed to solve part {part}: {e}"),
+    }
+}
+
+#[test]
+fn day01() {
+    let input = include_str!("examples/01.txt");
+    check::<aoc_2024_01::Day01>(input, 1, "11");
+    check::<aoc_2024_01::Day01>(input, 2, "31");
+}
+
+#[test]
+fn day02() {
+    let input = include_str!("examples/02.txt");
+    check::<aoc_2024_02::Day02>(input, 1, "2");
+    check::<aoc_2024_02::Day02>(input, 2, "4");
+}
+
+#[test]
+fn day03() {
+    check::<aoc_2024_03::Day03>(include_str!("examples/03.txt"), 1, "161");
+    check::<aoc_2024_03::Day03>(include_str!("examples/03-2.txt"), 2, "48");
+}
+
+#[test]
+fn day04() {
+    let input = include_str!("examples/04.txt");
+    check::<aoc_2024_04::Day04>(input, 1, "18");
+    check::<aoc_2024_04::Day04>(input, 2, "9");
+}
+
+#[test]
+fn day05() {
+    let input = include_str!("examples/05.txt");
+    check::<aoc_2024_05::Day05>(input, 1, "143");
+    check::<aoc_2024_05::Day05>(input, 2, "123");
+}
+
+#[test]
+fn day24() {
+    check::<aoc_2024_24::Day24>(include_str!("examples/24.txt"), 1, "2024");
+    // the published example of part 2 isn't an adder, so this one is a 6 bit adder
+    // with four swapped pairs of outputs
+    check::<aoc_2024_24::Day24>(
+        include_str!("examples/24-2.txt"),
+        2,
+        "cd03,co00,co04,hc01,hs01,z00,z03,z04",
+    );
+}
+
+#[test]
+fn day25() {
+    check::<aoc_2024_25::Day25>(include_str!("examples/25.txt"), 1, "3");
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+/// Errors reported while parsing the input or solving a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input doesn't have the expected format. Line and column are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        expected: String,
+    },
+    /// The input is well-formed, but the puzzle can't be solved for it.
+    Unsolvable(String),
+}
+
+impl Error {
+    /// Creates a parse error for `text`, which has to be a slice of `input`.
+    /// The slice is used to determine the line and column the error is reported at.
+    pub fn parse(input: &str, text: &str, expected: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (text.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| *offset <= input.len())
+            .unwrap_or(0);
+
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+
+        Error::Parse {
+            line,
+            column,
+            text: text.lines().next().unwrap_or("").to_string(),
+            expected: expected.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                text,
+                expected,
+            } => {
+                write!(f, "line {line}, column {column}: expected {expected}")?;
+                if text.is_empty() {
+                    write!(f, ", found end of line")
+                } else {
+                    write!(f, ", found `{text}`")
+                }
+            }
+            Error::Unsolvable(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
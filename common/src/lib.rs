@@ -0,0 +1,141 @@
+//! Input handling and the [`Solution`] trait shared by the solutions of all days.
+//!
+//! [`Rng`] is a small seeded random number generator for code that needs reproducible
+//! values, such as circuit verification and the generated benchmark inputs.
+
+use std::io::Read;
+use std::str::FromStr;
+
+mod error;
+mod rng;
+mod solution;
+
+pub use error::Error;
+pub use rng::Rng;
+pub use solution::{run, solve, Answer, Solution};
+
+/// A puzzle given as a grid of characters, one `Vec` per line.
+pub type Grid = Vec<Vec<u8>>;
+
+/// Reads the complete puzzle input from stdin.
+///
+/// Exits the process with an error message if stdin can't be read.
+pub fn read_input() -> String {
+    let mut input = String::new();
+
+    if let Err(e) = std::io::stdin().read_to_string(&mut input) {
+        eprintln!("Failed to read input data: {e}");
+        std::process::exit(1);
+    }
+
+    input
+}
+
+/// Returns the lines of the input without line endings, skipping blank lines.
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().filter(|line| !line.trim().is_empty())
+}
+
+/// Splits the input into sections separated by one or more blank lines.
+///
+/// Leading and trailing blank lines never produce empty sections.
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(s) = start.take() {
+                sections.push(&input[s..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end_matches(['\r', '\n']).len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(s) = start {
+        sections.push(&input[s..end]);
+    }
+
+    sections
+}
+
+/// Parses the non-blank lines of a section into a grid of bytes.
+pub fn parse_grid(section: &str) -> Grid {
+    lines(section)
+        .map(|line| line.trim_end().as_bytes().to_vec())
+        .collect()
+}
+
+/// Parses a list of integers separated by `separator` and/or whitespace.
+///
+/// `line` has to be a slice of `input`, which is used to report the location of errors.
+pub fn parse_integers<T: FromStr>(
+    input: &str,
+    line: &str,
+    separator: char,
+) -> Result<Vec<T>, Error> {
+    line.split(|c: char| c == separator || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<T>()
+                .map_err(|_| Error::parse(input, s, "an integer"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections() {
+        let input = "\na: 1\nb: 0\n\n\nx AND y -> z\r\n\r\nlast\n\n";
+        assert_eq!(sections(input), vec!["a: 1\nb: 0", "x AND y -> z", "last"]);
+        assert!(sections("").is_empty());
+        assert!(sections("\n \n").is_empty());
+    }
+
+    #[test]
+    fn test_lines() {
+        let actual: Vec<&str> = lines("1\n\n2\r\n  \n3").collect();
+        assert_eq!(actual, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        assert_eq!(parse_grid("XM\nAS\n"), vec![b"XM".to_vec(), b"AS".to_vec()]);
+    }
+
+    #[test]
+    fn test_parse_integers() {
+        let integers = |line, separator| parse_integers::<u64>(line, line, separator);
+        assert_eq!(integers("3   4", ' '), Ok(vec![3, 4]));
+        assert_eq!(integers("75,47, 61", ','), Ok(vec![75, 47, 61]));
+        assert_eq!(integers("47|53", '|'), Ok(vec![47, 53]));
+    }
+
+    #[test]
+    fn test_parse_integers_error() {
+        let input = "1,2\n3,x4";
+        let line = input.lines().nth(1).unwrap();
+        let error = parse_integers::<u64>(input, line, ',').unwrap_err();
+        assert_eq!(
+            error,
+            Error::Parse {
+                line: 2,
+                column: 3,
+                text: "x4".to_string(),
+                expected: "an integer".to_string(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected an integer, found `x4`"
+        );
+    }
+}
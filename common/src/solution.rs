@@ -0,0 +1,54 @@
+use std::fmt::Display;
+
+use crate::Error;
+
+/// The answer of a puzzle part, or the reason why it couldn't be solved.
+pub type Answer = Result<String, Error>;
+
+/// The solution of a single day, split into parsing the input and solving its parts.
+pub trait Solution {
+    /// The parsed puzzle input both parts work on.
+    type Input;
+
+    /// Number of parts of the puzzle. The last day of the calendar only has one.
+    const PARTS: u8 = 2;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    fn part1(input: &Self::Input) -> Result<impl Display, Error>;
+
+    fn part2(_input: &Self::Input) -> Result<impl Display, Error> {
+        Err::<String, _>(Error::Unsolvable("This puzzle has no part 2".to_string()))
+    }
+}
+
+/// Solves a single part of an already parsed puzzle and formats its answer.
+pub fn solve<S: Solution>(input: &S::Input, part: u8) -> Answer {
+    match part {
+        1 => S::part1(input).map(|answer| answer.to_string()),
+        2 if S::PARTS >= 2 => S::part2(input).map(|answer| answer.to_string()),
+        _ => Err(Error::Unsolvable(format!("This puzzle has no part {part}"))),
+    }
+}
+
+/// Reads the puzzle input from stdin and prints the answers of all parts, one per line.
+///
+/// Exits the process with an error message if the input can't be parsed or a part can't be
+/// solved.
+pub fn run<S: Solution>() {
+    let input = crate::read_input();
+
+    let answers = S::parse(&input)
+        .and_then(|input| (1..=S::PARTS).try_for_each(|part| print_answer::<S>(&input, part)));
+
+    if let Err(e) = answers {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
+
+fn print_answer<S: Solution>(input: &S::Input, part: u8) -> Result<(), Error> {
+    let answer = solve::<S>(input, part)?;
+    println!("{answer}");
+    Ok(())
+}